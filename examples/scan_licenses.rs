@@ -0,0 +1,33 @@
+use githubapi::{GitHubApi, LicensePolicy, OwnerKind, ToJsonString};
+use std::env;
+
+/// To run this example, you must first set two environment variables.
+/// ```bash
+/// export GH_USER="SandyClaws"
+/// export GH_PASS="ThisIsHalloween"
+///
+/// cargo run --example scan_licenses
+/// ```
+fn main() {
+    let username = env::var("GH_USER").expect("GH_USER not defined.");
+    let password = env::var("GH_PASS").expect("GH_PASS not defined.");
+
+    let gh = GitHubApi::new(&username, &password);
+
+    let policy = LicensePolicy::new()
+        .allow("MIT")
+        .allow("Apache-2.0")
+        .deny("GPL-3.0")
+        .deny("AGPL-3.0");
+
+    let report = gh
+        .scan_licenses(OwnerKind::Organization, "sous-chefs", &policy)
+        .unwrap();
+
+    println!("{}", report.to_csv_string());
+    println!("{}", report.to_json_string().unwrap());
+
+    for violation in report.violations() {
+        println!("{:?}: {}", violation.verdict, violation.repository);
+    }
+}
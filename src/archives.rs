@@ -0,0 +1,532 @@
+use crate::helpers::encode_file_path;
+use crate::types::*;
+use crate::{GitHubApi, API_URL};
+use flate2::read::GzDecoder;
+use reqwest::header::LOCATION;
+use reqwest::{Client, RedirectPolicy, Url};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Cursor, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tar::EntryType;
+use zip::ZipArchive;
+
+/// GitHub redirects archive downloads to codeload once, but leave some room.
+const MAX_REDIRECTS: usize = 10;
+
+/// The file type bits of a unix mode that mark a symbolic link.
+const SYMLINK_MODE: u32 = 0o120_000;
+
+/// Tells whether a request to `url` may carry the credentials.
+/// They are only ever sent to the API itself, never to the host it redirects to.
+fn sends_credentials(url: &Url) -> bool {
+    let api_url = Url::parse(API_URL).expect("API_URL is a valid url");
+
+    url.scheme() == "https" && url.host_str() == api_url.host_str()
+}
+
+/// Turns the path of an archive entry into a path relative to the destination.
+///
+/// GitHub puts everything inside a directory named after the repository and the commit,
+/// which is stripped. Returns `None` for that directory itself.
+fn entry_path(path: &Path) -> Result<Option<PathBuf>, GitHubApiError> {
+    let mut relative = PathBuf::new();
+
+    for (index, component) in path.components().enumerate() {
+        match component {
+            Component::Normal(_) if index == 0 => {}
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            _ => return Err(GitHubApiError::UnsafePath(path.display().to_string())),
+        }
+    }
+
+    if relative.as_os_str().is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(relative))
+    }
+}
+
+/// Makes sure a symbolic link at `target` resolves to somewhere inside `root`.
+///
+/// The link is followed one component at a time, resolving the links that are already on disk,
+/// so that a chain of links that each look harmless can't climb out of `root`.
+fn check_link_target(root: &Path, target: &Path, link: &Path) -> Result<(), GitHubApiError> {
+    let unsafe_link =
+        || GitHubApiError::UnsafePath(format!("{} -> {}", target.display(), link.display()));
+
+    // The link is relative to the directory it is in.
+    let mut resolved = target
+        .parent()
+        .unwrap_or(root)
+        .canonicalize()
+        .map_err(GitHubApiError::IoError)?;
+
+    for component in link.components() {
+        match component {
+            Component::Normal(part) => {
+                resolved.push(part);
+                if let Ok(canonical) = resolved.canonicalize() {
+                    resolved = canonical;
+                }
+            }
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            _ => return Err(unsafe_link()),
+        }
+
+        if !resolved.starts_with(root) {
+            return Err(unsafe_link());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the symbolic links again once everything is unpacked,
+/// since a later entry can change where an earlier link points.
+/// Links that now lead outside of `root` are removed.
+fn check_links(root: &Path, links: &[PathBuf]) -> Result<(), GitHubApiError> {
+    let mut result = Ok(());
+
+    for path in links {
+        // A later entry may have replaced the link.
+        match fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {}
+            _ => continue,
+        }
+
+        let link = fs::read_link(path).map_err(GitHubApiError::IoError)?;
+        if let Err(error) = check_link_target(root, path, &link) {
+            fs::remove_file(path).map_err(GitHubApiError::IoError)?;
+            if result.is_ok() {
+                result = Err(error);
+            }
+        }
+    }
+
+    result
+}
+
+/// Creates the parent directories of `relative` inside `root`,
+/// and makes sure they didn't end up outside of it by way of a symbolic link.
+/// Returns the path with its parent directories resolved.
+///
+/// Whatever an earlier entry left at the path itself is removed,
+/// so that writing to it can't follow a symbolic link.
+fn prepare_target(root: &Path, relative: &Path) -> Result<PathBuf, GitHubApiError> {
+    let unsafe_path = || GitHubApiError::UnsafePath(relative.display().to_string());
+    let target = root.join(relative);
+    let parent = target.parent().unwrap_or(root);
+    let name = target.file_name().ok_or_else(unsafe_path)?;
+
+    fs::create_dir_all(parent).map_err(GitHubApiError::IoError)?;
+
+    let parent = parent.canonicalize().map_err(GitHubApiError::IoError)?;
+    if !parent.starts_with(root) {
+        return Err(unsafe_path());
+    }
+
+    let target = parent.join(name);
+
+    match fs::symlink_metadata(&target) {
+        Ok(metadata) if !metadata.is_dir() => {
+            fs::remove_file(&target).map_err(GitHubApiError::IoError)?
+        }
+        _ => {}
+    }
+
+    Ok(target)
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+#[cfg(not(unix))]
+fn create_symlink(target: &Path, path: &Path) -> io::Result<()> {
+    fs::write(path, target.to_string_lossy().as_bytes())
+}
+
+fn unpack_tarball<R>(reader: R, root: &Path) -> Result<Vec<PathBuf>, GitHubApiError>
+where
+    R: Read,
+{
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    let mut unpacked = Vec::new();
+    let mut links = Vec::new();
+
+    for entry in archive.entries().map_err(GitHubApiError::IoError)? {
+        let mut entry = entry.map_err(GitHubApiError::IoError)?;
+        let path = entry.path().map_err(GitHubApiError::IoError)?.into_owned();
+        let relative = match entry_path(&path)? {
+            Some(relative) => relative,
+            None => continue,
+        };
+
+        match entry.header().entry_type() {
+            EntryType::Directory => {
+                let target = prepare_target(root, &relative)?;
+                fs::create_dir_all(&target).map_err(GitHubApiError::IoError)?;
+            }
+            EntryType::Regular | EntryType::Continuous => {
+                let target = prepare_target(root, &relative)?;
+                entry.unpack(&target).map_err(GitHubApiError::IoError)?;
+            }
+            EntryType::Symlink => {
+                let link = entry.link_name().map_err(GitHubApiError::IoError)?;
+                let link =
+                    link.ok_or_else(|| GitHubApiError::UnsafePath(relative.display().to_string()))?;
+                let target = prepare_target(root, &relative)?;
+                check_link_target(root, &target, &link)?;
+                create_symlink(&link, &target).map_err(GitHubApiError::IoError)?;
+                links.push(target);
+            }
+            // Global headers, hard links and the like are skipped.
+            _ => continue,
+        }
+
+        unpacked.push(relative);
+    }
+
+    check_links(root, &links)?;
+    Ok(unpacked)
+}
+
+fn unpack_zipball<R>(mut reader: R, root: &Path) -> Result<Vec<PathBuf>, GitHubApiError>
+where
+    R: Read,
+{
+    // Zip files keep their index at the end, so the whole file is needed first.
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(GitHubApiError::IoError)?;
+
+    let mut archive = ZipArchive::new(Cursor::new(bytes))
+        .map_err(|error| GitHubApiError::DecodeError(error.to_string()))?;
+    let mut unpacked = Vec::new();
+    let mut links = Vec::new();
+
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .map_err(|error| GitHubApiError::DecodeError(error.to_string()))?;
+        let relative = match entry_path(Path::new(file.name()))? {
+            Some(relative) => relative,
+            None => continue,
+        };
+        let target = prepare_target(root, &relative)?;
+
+        if file.is_dir() {
+            fs::create_dir_all(&target).map_err(GitHubApiError::IoError)?;
+        } else if file.unix_mode().unwrap_or(0) & 0o170_000 == SYMLINK_MODE {
+            let mut link = String::new();
+            file.read_to_string(&mut link)
+                .map_err(GitHubApiError::IoError)?;
+            check_link_target(root, &target, Path::new(&link))?;
+            create_symlink(Path::new(&link), &target).map_err(GitHubApiError::IoError)?;
+            links.push(target);
+        } else {
+            let mut output = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&target)
+                .map_err(GitHubApiError::IoError)?;
+            io::copy(&mut file, &mut output).map_err(GitHubApiError::IoError)?;
+        }
+
+        unpacked.push(relative);
+    }
+
+    check_links(root, &links)?;
+    Ok(unpacked)
+}
+
+/// Unpacks an archive downloaded from GitHub into `destination`, which is created if needed.
+/// Returns the paths that were unpacked, relative to `destination`.
+///
+/// The top-level directory GitHub wraps the files in is left out.
+/// Entries that would end up outside of `destination` are rejected with `UnsafePath`.
+pub fn unpack_archive<R>(
+    format: ArchiveFormat,
+    reader: R,
+    destination: &Path,
+) -> Result<Vec<PathBuf>, GitHubApiError>
+where
+    R: Read,
+{
+    fs::create_dir_all(destination).map_err(GitHubApiError::IoError)?;
+    let root = destination
+        .canonicalize()
+        .map_err(GitHubApiError::IoError)?;
+
+    match format {
+        ArchiveFormat::Tarball => unpack_tarball(reader, &root),
+        ArchiveFormat::Zipball => unpack_zipball(reader, &root),
+    }
+}
+
+/// Implement the archive endpoints.
+impl GitHubApi {
+    /// Streams the archive of any branch, tag or commit into `writer`.
+    /// Returns the number of bytes written.
+    pub fn download_archive<W>(
+        &self,
+        owner: &str,
+        repository: &str,
+        format: ArchiveFormat,
+        reference: &str,
+        writer: &mut W,
+    ) -> Result<u64, GitHubApiError>
+    where
+        W: Write + ?Sized,
+    {
+        let url = archive_url(owner, repository, format, reference);
+        self.download_archive_url(&url, writer)
+    }
+
+    /// Streams an archive from a url such as the `tarball_url` of a tag or a release.
+    pub fn download_archive_url<W>(&self, url: &str, writer: &mut W) -> Result<u64, GitHubApiError>
+    where
+        W: Write + ?Sized,
+    {
+        let mut response = self.open_archive(url)?;
+        io::copy(&mut response, writer).map_err(GitHubApiError::IoError)
+    }
+
+    /// Downloads the archive of any branch, tag or commit, and unpacks it into `destination`.
+    /// See `unpack_archive`.
+    pub fn extract_archive(
+        &self,
+        owner: &str,
+        repository: &str,
+        format: ArchiveFormat,
+        reference: &str,
+        destination: &Path,
+    ) -> Result<Vec<PathBuf>, GitHubApiError> {
+        let url = archive_url(owner, repository, format, reference);
+        unpack_archive(format, self.open_archive(&url)?, destination)
+    }
+
+    /// Follows the redirects of an archive url by hand,
+    /// so that the credentials don't leave the API host.
+    fn open_archive(&self, url: &str) -> Result<reqwest::Response, GitHubApiError> {
+        let client = Client::builder()
+            .redirect(RedirectPolicy::none())
+            .build()
+            .map_err(GitHubApiError::ReqwestError)?;
+        let mut url = Url::parse(url).map_err(|_| GitHubApiError::InvalidUrl(url.to_string()))?;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let mut request = client.get(url.clone());
+            if sends_credentials(&url) {
+                request = request.basic_auth(&self.username, Some(&self.password));
+            }
+
+            let mut response = request.send().map_err(GitHubApiError::ReqwestError)?;
+            let status = response.status();
+
+            if status.is_success() {
+                return Ok(response);
+            }
+
+            if !status.is_redirection() {
+                return Err(GitHubApiError::GitHubError((
+                    status.to_string(),
+                    response.text().unwrap_or_default(),
+                )));
+            }
+
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|it| it.to_str().ok())
+                .ok_or_else(|| GitHubApiError::InvalidUrl(url.to_string()))?;
+            url = url
+                .join(location)
+                .map_err(|_| GitHubApiError::InvalidUrl(location.to_string()))?;
+        }
+
+        Err(GitHubApiError::InvalidUrl(url.to_string()))
+    }
+}
+
+fn archive_url(owner: &str, repository: &str, format: ArchiveFormat, reference: &str) -> String {
+    let format = match format {
+        ArchiveFormat::Tarball => "tarball",
+        ArchiveFormat::Zipball => "zipball",
+    };
+
+    format!(
+        "{}repos/{}/{}/{}/{}",
+        API_URL,
+        owner,
+        repository,
+        format,
+        encode_file_path(reference)
+    )
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credentials_stay_on_the_api_host() {
+        let api = Url::parse("https://api.github.com/repos/a/b/tarball/main").unwrap();
+        let codeload = Url::parse("https://codeload.github.com/a/b/legacy.tar.gz/main").unwrap();
+        let plain = Url::parse("http://api.github.com/repos/a/b/tarball/main").unwrap();
+
+        assert!(sends_credentials(&api));
+        assert!(!sends_credentials(&codeload));
+        assert!(!sends_credentials(&plain));
+    }
+
+    #[test]
+    fn entry_paths_cannot_escape_the_destination() {
+        let path = |it: &str| entry_path(Path::new(it));
+
+        assert_eq!(path("a-b-123/").unwrap(), None);
+        assert_eq!(
+            path("a-b-123/src/lib.rs").unwrap(),
+            Some(PathBuf::from("src/lib.rs"))
+        );
+        assert!(path("a-b-123/../../etc/passwd").is_err());
+        assert!(path("/etc/passwd").is_err());
+    }
+
+    /// Builds a zip out of directories, files and symbolic links.
+    fn zip(entries: &[(&str, Option<&str>, bool)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+        for (name, content, _) in entries {
+            match content {
+                Some(content) => {
+                    writer
+                        .start_file(*name, zip::write::FileOptions::default())
+                        .unwrap();
+                    writer.write_all(content.as_bytes()).unwrap();
+                }
+                None => writer
+                    .add_directory(*name, zip::write::FileOptions::default())
+                    .unwrap(),
+            }
+        }
+
+        let mut bytes = writer.finish().unwrap().into_inner();
+
+        // The writer can't make links, so mark them as such in the central directory.
+        let signature = [0x50, 0x4b, 0x01, 0x02];
+        let headers: Vec<usize> = (0..bytes.len() - 4)
+            .filter(|it| bytes[*it..*it + 4] == signature)
+            .collect();
+        for (offset, (_, _, link)) in headers.into_iter().zip(entries) {
+            if *link {
+                let attributes = (SYMLINK_MODE | 0o777) << 16;
+                bytes[offset + 38..offset + 42].copy_from_slice(&attributes.to_le_bytes());
+            }
+        }
+
+        bytes
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn chained_links_cannot_escape_the_destination() {
+        let bytes = zip(&[
+            ("r/", None, false),
+            ("r/a/", None, false),
+            ("r/a/l1", Some(".."), true),
+            ("r/x", Some("a/l1/../../y"), true),
+            ("r/x", Some("pwned"), false),
+        ]);
+
+        let base = std::env::temp_dir().join(format!("githubapi-zip-{}", std::process::id()));
+        let destination = base.join("destination");
+        let result = unpack_archive(ArchiveFormat::Zipball, &bytes[..], &destination);
+        let escaped = base.join("y").exists();
+        fs::remove_dir_all(&base).unwrap();
+
+        assert!(matches!(result, Err(GitHubApiError::UnsafePath(_))));
+        assert!(!escaped);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn later_links_cannot_redirect_earlier_ones() {
+        let bytes = zip(&[
+            ("r/", None, false),
+            ("r/x", Some("m/outside"), true),
+            ("r/m", Some("n/.."), true),
+            ("r/n", Some("."), true),
+        ]);
+
+        let base = std::env::temp_dir().join(format!("githubapi-order-{}", std::process::id()));
+        let destination = base.join("destination");
+        fs::create_dir_all(base.join("outside")).unwrap();
+        fs::write(base.join("outside/secret"), "secret").unwrap();
+        let result = unpack_archive(ArchiveFormat::Zipball, &bytes[..], &destination);
+        let escaped = fs::read_to_string(destination.join("x/secret")).is_ok();
+        let x_kept = fs::symlink_metadata(destination.join("x")).is_ok();
+        let m_kept = fs::symlink_metadata(destination.join("m")).is_ok();
+        fs::remove_dir_all(&base).unwrap();
+
+        assert!(matches!(result, Err(GitHubApiError::UnsafePath(_))));
+        assert!(!escaped);
+        assert!(!x_kept);
+        assert!(!m_kept);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn links_inside_the_destination_are_kept() {
+        let bytes = zip(&[
+            ("r/", None, false),
+            ("r/src/", None, false),
+            ("r/README.md", Some("hello"), false),
+            ("r/src/README.md", Some("../README.md"), true),
+        ]);
+
+        let destination =
+            std::env::temp_dir().join(format!("githubapi-links-{}", std::process::id()));
+        let unpacked = unpack_archive(ArchiveFormat::Zipball, &bytes[..], &destination).unwrap();
+        let linked = fs::read_to_string(destination.join("src/README.md")).unwrap();
+        fs::remove_dir_all(&destination).unwrap();
+
+        assert_eq!(unpacked.len(), 3);
+        assert_eq!(linked, "hello");
+    }
+
+    #[test]
+    fn tarballs_are_unpacked_without_the_top_level_directory() {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let content = b"fn main() {}";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "a-b-123/src/main.rs", &content[..])
+            .unwrap();
+        let bytes = builder.into_inner().unwrap().finish().unwrap();
+
+        let destination = std::env::temp_dir().join(format!("githubapi-{}", std::process::id()));
+        let unpacked = unpack_archive(ArchiveFormat::Tarball, &bytes[..], &destination).unwrap();
+        let written = fs::read(destination.join("src/main.rs")).unwrap();
+        fs::remove_dir_all(&destination).unwrap();
+
+        assert_eq!(unpacked, vec![PathBuf::from("src/main.rs")]);
+        assert_eq!(written, content);
+    }
+}
+
+// endregion
@@ -0,0 +1,485 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::helpers::encode_file_path;
+use crate::impl_repository_scope;
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListBranches {
+    pub owner: String,
+    pub repository: String,
+    pub query: ListBranchesQuery,
+}
+
+impl Endpoint for ListBranches {
+    type Response = Vec<BranchSummary>;
+    type Query = ListBranchesQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/branches", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &ListBranchesQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBranch {
+    pub owner: String,
+    pub repository: String,
+    pub branch: String,
+}
+
+impl Endpoint for GetBranch {
+    type Response = Branch;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/branches/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.branch)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameBranch {
+    pub owner: String,
+    pub repository: String,
+    pub branch: String,
+    pub new_name: String,
+}
+
+impl Endpoint for RenameBranch {
+    type Response = Branch;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/branches/{}/rename",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.branch)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        Some(serde_json::json!({ "new_name": self.new_name }))
+    }
+
+    impl_repository_scope!();
+}
+
+/// Merges `head` into the `base` branch.
+/// Answers with the merge commit, or with nothing if `base` already contains `head`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeBranches {
+    pub owner: String,
+    pub repository: String,
+    pub merge: BranchMerge,
+}
+
+impl Endpoint for MergeBranches {
+    type Response = Option<Commit>;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/merges", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.merge).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBranchProtection {
+    pub owner: String,
+    pub repository: String,
+    pub branch: String,
+}
+
+impl Endpoint for GetBranchProtection {
+    type Response = BranchProtection;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/branches/{}/protection",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.branch)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+/// Replaces the whole protection of a branch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateBranchProtection {
+    pub owner: String,
+    pub repository: String,
+    pub branch: String,
+    pub protection: BranchProtectionUpdate,
+}
+
+impl Endpoint for UpdateBranchProtection {
+    type Response = BranchProtection;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/branches/{}/protection",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.branch)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.protection).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteBranchProtection {
+    pub owner: String,
+    pub repository: String,
+    pub branch: String,
+}
+
+impl Endpoint for DeleteBranchProtection {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/branches/{}/protection",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.branch)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+/// Signatures are managed apart from the rest of the protection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnableRequiredSignatures {
+    pub owner: String,
+    pub repository: String,
+    pub branch: String,
+}
+
+impl Endpoint for EnableRequiredSignatures {
+    type Response = ProtectionToggle;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/branches/{}/protection/required_signatures",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.branch)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisableRequiredSignatures {
+    pub owner: String,
+    pub repository: String,
+    pub branch: String,
+}
+
+impl Endpoint for DisableRequiredSignatures {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/branches/{}/protection/required_signatures",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.branch)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+pub type BranchPaginator<'a> = Paginator<'a, ListBranches>;
+
+/// Implement the branch endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the branches of a repository.
+    pub fn list_branches(
+        &self,
+        owner: &str,
+        repository: &str,
+        query: &ListBranchesQuery,
+    ) -> BranchPaginator<'_> {
+        self.paginate(ListBranches {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            query: query.clone(),
+        })
+    }
+
+    pub fn get_branch(&self, owner: &str, repository: &str, branch: &str) -> Response<Branch> {
+        self.get_endpoint(&GetBranch {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            branch: branch.to_string(),
+        })
+    }
+
+    /// Renames a branch. GitHub updates pull requests and branch protection to match.
+    pub fn rename_branch(
+        &self,
+        owner: &str,
+        repository: &str,
+        branch: &str,
+        new_name: &str,
+    ) -> Response<Branch> {
+        self.get_endpoint(&RenameBranch {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            branch: branch.to_string(),
+            new_name: new_name.to_string(),
+        })
+    }
+
+    /// Merges `head` into the `base` branch.
+    /// The result is `None` if `base` already contains `head`.
+    pub fn merge_branches(
+        &self,
+        owner: &str,
+        repository: &str,
+        base: &str,
+        head: &str,
+        commit_message: Option<&str>,
+    ) -> Response<Option<Commit>> {
+        self.get_endpoint(&MergeBranches {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            merge: BranchMerge {
+                base: base.to_string(),
+                head: head.to_string(),
+                commit_message: commit_message.map(str::to_string),
+            },
+        })
+    }
+}
+
+/// Implement the branch protection endpoints.
+impl GitHubApi {
+    /// Gets the protection of a branch. This is "404 Not Found" if it isn't protected.
+    pub fn get_branch_protection(
+        &self,
+        owner: &str,
+        repository: &str,
+        branch: &str,
+    ) -> Response<BranchProtection> {
+        self.get_endpoint(&GetBranchProtection {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            branch: branch.to_string(),
+        })
+    }
+
+    /// Replaces the whole protection of a branch, except for required signatures.
+    pub fn update_branch_protection(
+        &self,
+        owner: &str,
+        repository: &str,
+        branch: &str,
+        protection: &BranchProtectionUpdate,
+    ) -> Response<BranchProtection> {
+        self.get_endpoint(&UpdateBranchProtection {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            branch: branch.to_string(),
+            protection: protection.clone(),
+        })
+    }
+
+    pub fn delete_branch_protection(
+        &self,
+        owner: &str,
+        repository: &str,
+        branch: &str,
+    ) -> Response<()> {
+        self.get_endpoint(&DeleteBranchProtection {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            branch: branch.to_string(),
+        })
+    }
+
+    /// Requires commits on a protected branch to be signed, or stops requiring it.
+    pub fn set_required_signatures(
+        &self,
+        owner: &str,
+        repository: &str,
+        branch: &str,
+        enabled: bool,
+    ) -> Result<(), GitHubApiError> {
+        if enabled {
+            self.get_endpoint(&EnableRequiredSignatures {
+                owner: owner.to_string(),
+                repository: repository.to_string(),
+                branch: branch.to_string(),
+            })?;
+        } else {
+            self.get_endpoint(&DisableRequiredSignatures {
+                owner: owner.to_string(),
+                repository: repository.to_string(),
+                branch: branch.to_string(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies a protection baseline to the default branch of a repository,
+    /// including required signatures, which GitHub manages separately.
+    pub fn protect_default_branch(
+        &self,
+        owner: &str,
+        repository: &str,
+        protection: &BranchProtectionUpdate,
+        required_signatures: bool,
+    ) -> Response<BranchProtection> {
+        let branch = self
+            .get_repository(owner, repository)?
+            .result
+            .default_branch;
+        let response = self.update_branch_protection(owner, repository, &branch, protection)?;
+        self.set_required_signatures(owner, repository, &branch, required_signatures)?;
+
+        Ok(response)
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_leave_out_a_missing_commit_message() {
+        let merge = MergeBranches {
+            owner: "a".to_string(),
+            repository: "b".to_string(),
+            merge: BranchMerge {
+                base: "main".to_string(),
+                head: "feature".to_string(),
+                commit_message: None,
+            },
+        };
+
+        assert_eq!(
+            merge.body().unwrap().to_string(),
+            r#"{"base":"main","head":"feature"}"#
+        );
+    }
+
+    #[test]
+    fn protection_always_sends_the_required_fields() {
+        let protection = BranchProtectionUpdate {
+            enforce_admins: Some(true),
+            required_linear_history: Some(true),
+            ..BranchProtectionUpdate::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&protection).unwrap(),
+            r#"{"required_status_checks":null,"enforce_admins":true,"required_pull_request_reviews":null,"restrictions":null,"required_linear_history":true}"#
+        );
+    }
+
+    #[test]
+    fn strict_status_checks_send_an_empty_list_of_checks() {
+        let protection = BranchProtectionUpdate {
+            required_status_checks: Some(RequiredStatusChecks {
+                strict: true,
+                checks: Vec::new(),
+            }),
+            ..BranchProtectionUpdate::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&protection).unwrap()["required_status_checks"],
+            serde_json::json!({"strict": true, "checks": []})
+        );
+    }
+}
+
+// endregion
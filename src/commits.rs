@@ -0,0 +1,342 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::helpers::encode_file_path;
+use crate::impl_repository_scope;
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListCommits {
+    pub owner: String,
+    pub repository: String,
+    pub query: ListCommitsQuery,
+}
+
+impl Endpoint for ListCommits {
+    type Response = Vec<Commit>;
+    type Query = ListCommitsQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/commits", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &ListCommitsQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+/// Gets a commit by SHA, branch or tag, including its files and stats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetCommit {
+    pub owner: String,
+    pub repository: String,
+    pub reference: String,
+}
+
+impl Endpoint for GetCommit {
+    type Response = Commit;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/commits/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.reference)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareCommits {
+    pub owner: String,
+    pub repository: String,
+    pub base: String,
+    pub head: String,
+}
+
+impl Endpoint for CompareCommits {
+    type Response = Comparison;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/compare/{}...{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.base),
+            encode_file_path(&self.head)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetCombinedStatus {
+    pub owner: String,
+    pub repository: String,
+    pub reference: String,
+}
+
+impl Endpoint for GetCombinedStatus {
+    type Response = CombinedStatus;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/commits/{}/status",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.reference)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListCommitStatuses {
+    pub owner: String,
+    pub repository: String,
+    pub reference: String,
+}
+
+impl Endpoint for ListCommitStatuses {
+    type Response = Vec<CommitStatus>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/commits/{}/statuses",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.reference)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCommitStatus {
+    pub owner: String,
+    pub repository: String,
+    pub sha: String,
+    pub status: NewCommitStatus,
+}
+
+impl Endpoint for CreateCommitStatus {
+    type Response = CommitStatus;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/statuses/{}",
+            self.owner, self.repository, self.sha
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.status).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+pub type CommitPaginator<'a> = Paginator<'a, ListCommits>;
+pub type CommitStatusPaginator<'a> = Paginator<'a, ListCommitStatuses>;
+
+/// Implement the commit endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the commits of a repository, newest first.
+    pub fn list_commits(
+        &self,
+        owner: &str,
+        repository: &str,
+        query: &ListCommitsQuery,
+    ) -> CommitPaginator<'_> {
+        self.paginate(ListCommits {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            query: query.clone(),
+        })
+    }
+
+    /// Gets a commit by SHA, branch or tag, including its files and stats.
+    pub fn get_commit(&self, owner: &str, repository: &str, reference: &str) -> Response<Commit> {
+        self.get_endpoint(&GetCommit {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            reference: reference.to_string(),
+        })
+    }
+
+    /// Gets the full commit that a tag points to.
+    pub fn resolve_commit(
+        &self,
+        owner: &str,
+        repository: &str,
+        commit: &TagsCommit,
+    ) -> Response<Commit> {
+        self.get_commit(owner, repository, &commit.sha)
+    }
+
+    /// Compares two commits, branches or tags. Use "user:branch" for branches in forks.
+    pub fn compare_commits(
+        &self,
+        owner: &str,
+        repository: &str,
+        base: &str,
+        head: &str,
+    ) -> Response<Comparison> {
+        self.get_endpoint(&CompareCommits {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            base: base.to_string(),
+            head: head.to_string(),
+        })
+    }
+
+    /// Gets the latest status of every context, and what they add up to.
+    pub fn get_combined_status(
+        &self,
+        owner: &str,
+        repository: &str,
+        reference: &str,
+    ) -> Response<CombinedStatus> {
+        self.get_endpoint(&GetCombinedStatus {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            reference: reference.to_string(),
+        })
+    }
+
+    /// Creates a paginator over every status of a commit, newest first.
+    pub fn list_commit_statuses(
+        &self,
+        owner: &str,
+        repository: &str,
+        reference: &str,
+    ) -> CommitStatusPaginator<'_> {
+        self.paginate(ListCommitStatuses {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            reference: reference.to_string(),
+        })
+    }
+
+    pub fn create_commit_status(
+        &self,
+        owner: &str,
+        repository: &str,
+        sha: &str,
+        status: &NewCommitStatus,
+    ) -> Response<CommitStatus> {
+        self.get_endpoint(&CreateCommitStatus {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            sha: sha.to_string(),
+            status: status.clone(),
+        })
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{parse_json, UncapturedFields};
+
+    #[test]
+    fn references_are_encoded_in_paths() {
+        let status = GetCombinedStatus {
+            owner: "a".to_string(),
+            repository: "b".to_string(),
+            reference: "feature/a#b".to_string(),
+        };
+        let comparison = CompareCommits {
+            owner: "a".to_string(),
+            repository: "b".to_string(),
+            base: "main".to_string(),
+            head: "feature/a b".to_string(),
+        };
+
+        assert_eq!(status.path(), "repos/a/b/commits/feature/a%23b/status");
+        assert_eq!(comparison.path(), "repos/a/b/compare/main...feature/a%20b");
+    }
+
+    #[test]
+    fn comparisons_have_ahead_and_behind_counts() {
+        let commit = r#"{
+            "url": "https://api.github.com/repos/a/b/commits/abc",
+            "sha": "abc",
+            "node_id": "C_1",
+            "html_url": "https://github.com/a/b/commit/abc",
+            "comments_url": "https://api.github.com/repos/a/b/commits/abc/comments",
+            "commit": {
+                "url": "https://api.github.com/repos/a/b/git/commits/abc",
+                "author": {"name": "A", "email": "a@example.com", "date": "2020-01-31T00:00:00Z"},
+                "committer": null,
+                "message": "Fix it",
+                "tree": {"sha": "def", "url": "https://api.github.com/repos/a/b/git/trees/def"},
+                "comment_count": 0
+            },
+            "author": null,
+            "committer": null,
+            "parents": []
+        }"#;
+        let text = format!(
+            r#"{{
+                "url": "", "html_url": "", "permalink_url": "", "diff_url": "", "patch_url": "",
+                "base_commit": {0}, "merge_base_commit": {0},
+                "status": "diverged", "ahead_by": 2, "behind_by": 1, "total_commits": 2,
+                "commits": [{0}], "files": []
+            }}"#,
+            commit
+        );
+
+        let comparison: Comparison = parse_json(&text).unwrap();
+
+        assert_eq!(comparison.status, ComparisonStatus::Diverged);
+        assert_eq!((comparison.ahead_by, comparison.behind_by), (2, 1));
+        assert_eq!(comparison.commits[0].commit.message, "Fix it");
+        assert!(comparison.uncaptured_fields().is_empty());
+    }
+}
+
+// endregion
@@ -0,0 +1,332 @@
+use crate::helpers::ToJsonString;
+use crate::impl_to_json_string;
+use crate::types::*;
+use crate::GitHubApi;
+use serde::{Deserialize, Serialize};
+use serde_json::error::Error as JsonError;
+use std::collections::BTreeMap;
+
+/// The name used to group repositories without a license.
+pub const NO_LICENSE: &str = "NONE";
+
+#[derive(Debug, Clone, Copy)]
+pub enum OwnerKind {
+    Organization,
+    User,
+}
+
+/// Lists of SPDX identifiers, such as "MIT" or "GPL-3.0".
+/// An empty allow list allows everything that isn't denied.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LicensePolicy {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+impl LicensePolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow(mut self, spdx_id: &str) -> Self {
+        self.allow.push(spdx_id.to_string());
+        self
+    }
+
+    pub fn deny(mut self, spdx_id: &str) -> Self {
+        self.deny.push(spdx_id.to_string());
+        self
+    }
+
+    pub fn evaluate(&self, spdx_id: Option<&str>) -> LicenseVerdict {
+        let spdx_id = match spdx_id {
+            Some(spdx_id) => spdx_id,
+            None => return LicenseVerdict::Unlicensed,
+        };
+
+        // SPDX identifiers are case insensitive.
+        let listed = |list: &[String]| list.iter().any(|it| it.eq_ignore_ascii_case(spdx_id));
+
+        if listed(&self.deny) {
+            LicenseVerdict::Denied
+        } else if self.allow.is_empty() || listed(&self.allow) {
+            LicenseVerdict::Allowed
+        } else {
+            LicenseVerdict::Unlisted
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LicenseVerdict {
+    Allowed,
+    Denied,
+    Unlisted,
+    Unlicensed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepositoryLicense {
+    pub repository: String,
+    pub spdx_id: Option<String>,
+    pub license_name: Option<String>,
+    pub verdict: LicenseVerdict,
+}
+impl_to_json_string!(RepositoryLicense);
+
+impl RepositoryLicense {
+    pub fn new(
+        repository: String,
+        license: Option<LicenseLicense>,
+        policy: &LicensePolicy,
+    ) -> Self {
+        let spdx_id = license.as_ref().map(|it| it.spdx_id.clone());
+        let verdict = policy.evaluate(spdx_id.as_deref());
+
+        Self {
+            repository,
+            spdx_id,
+            license_name: license.map(|it| it.name),
+            verdict,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LicenseReport {
+    pub owner: String,
+    pub policy: LicensePolicy,
+    pub repositories: Vec<RepositoryLicense>,
+
+    /// Repository names, grouped by SPDX identifier.
+    /// Repositories without a license are grouped under `NO_LICENSE`.
+    pub by_license: BTreeMap<String, Vec<String>>,
+}
+impl_to_json_string!(LicenseReport);
+
+impl LicenseReport {
+    pub fn new(owner: &str, policy: &LicensePolicy, repositories: Vec<RepositoryLicense>) -> Self {
+        let mut by_license: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for repository in &repositories {
+            let key = repository.spdx_id.as_deref().unwrap_or(NO_LICENSE);
+            by_license
+                .entry(key.to_string())
+                .or_default()
+                .push(repository.repository.clone());
+        }
+
+        for names in by_license.values_mut() {
+            names.sort();
+        }
+
+        Self {
+            owner: owner.to_string(),
+            policy: policy.clone(),
+            repositories,
+            by_license,
+        }
+    }
+
+    /// Gets the repositories that aren't allowed by the policy.
+    pub fn violations(&self) -> Vec<&RepositoryLicense> {
+        self.repositories
+            .iter()
+            .filter(|it| it.verdict != LicenseVerdict::Allowed)
+            .collect()
+    }
+
+    /// Writes one line per repository, grouped by license.
+    pub fn to_csv_string(&self) -> String {
+        let mut repositories: Vec<_> = self.repositories.iter().collect();
+        repositories.sort_by(|a, b| {
+            let a_key = (a.spdx_id.as_deref().unwrap_or(NO_LICENSE), &a.repository);
+            let b_key = (b.spdx_id.as_deref().unwrap_or(NO_LICENSE), &b.repository);
+            a_key.cmp(&b_key)
+        });
+
+        let mut csv = String::from("spdx_id,license_name,repository,verdict\n");
+
+        for repository in repositories {
+            let verdict = match repository.verdict {
+                LicenseVerdict::Allowed => "allowed",
+                LicenseVerdict::Denied => "denied",
+                LicenseVerdict::Unlisted => "unlisted",
+                LicenseVerdict::Unlicensed => "unlicensed",
+            };
+
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                csv_field(repository.spdx_id.as_deref().unwrap_or(NO_LICENSE)),
+                csv_field(repository.license_name.as_deref().unwrap_or("")),
+                csv_field(&repository.repository),
+                verdict
+            ));
+        }
+
+        csv
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Implement the license compliance scanner.
+impl GitHubApi {
+    /// Gets the license of every repository belonging to an organization or a user,
+    /// and checks it against the policy.
+    ///
+    /// The license is taken from the repository listing,
+    /// and only looked up when the listing doesn't have one.
+    pub fn scan_licenses(
+        &self,
+        owner_kind: OwnerKind,
+        owner: &str,
+        policy: &LicensePolicy,
+    ) -> Result<LicenseReport, GitHubApiError> {
+        self.scan_repository_licenses(owner_kind, owner, policy, false)
+    }
+
+    /// Same as `scan_licenses`, but looks up the license of every repository
+    /// instead of trusting the repository listing.
+    pub fn scan_licenses_in_detail(
+        &self,
+        owner_kind: OwnerKind,
+        owner: &str,
+        policy: &LicensePolicy,
+    ) -> Result<LicenseReport, GitHubApiError> {
+        self.scan_repository_licenses(owner_kind, owner, policy, true)
+    }
+
+    fn scan_repository_licenses(
+        &self,
+        owner_kind: OwnerKind,
+        owner: &str,
+        policy: &LicensePolicy,
+        detailed: bool,
+    ) -> Result<LicenseReport, GitHubApiError> {
+        let query = ListRepositoriesQuery::default();
+        let paginator = match owner_kind {
+            OwnerKind::Organization => self.list_org_repos(owner, &query),
+            OwnerKind::User => self.list_user_repos(owner, &query),
+        };
+
+        let mut repositories = Vec::new();
+        let mut next_page = Some(PageCursor::Page(1));
+
+        while let Some(cursor) = next_page {
+            let response = paginator.get_cursor(&cursor)?;
+            next_page = response.next_link.map(|it| PageCursor::Url(it.url));
+
+            for Repository { name, license, .. } in response.result {
+                let license = match license {
+                    Some(license) if !detailed => Some(license),
+                    _ => match self.get_license(owner, &name) {
+                        Ok(response) => Some(response.result.license),
+                        Err(ref error) if error.is_not_found() => None,
+                        Err(error) => return Err(error),
+                    },
+                };
+
+                repositories.push(RepositoryLicense::new(name, license, policy));
+            }
+        }
+
+        Ok(LicenseReport::new(owner, policy, repositories))
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repository(name: &str, spdx_id: Option<&str>, policy: &LicensePolicy) -> RepositoryLicense {
+        RepositoryLicense {
+            repository: name.to_string(),
+            spdx_id: spdx_id.map(str::to_string),
+            license_name: spdx_id.map(|it| format!("The {} license, with, commas", it)),
+            verdict: policy.evaluate(spdx_id),
+        }
+    }
+
+    #[test]
+    fn policy_evaluates_spdx_ids() {
+        let policy = LicensePolicy::new().allow("MIT").deny("GPL-3.0");
+
+        assert_eq!(policy.evaluate(Some("mit")), LicenseVerdict::Allowed);
+        assert_eq!(policy.evaluate(Some("GPL-3.0")), LicenseVerdict::Denied);
+        assert_eq!(
+            policy.evaluate(Some("Apache-2.0")),
+            LicenseVerdict::Unlisted
+        );
+        assert_eq!(policy.evaluate(None), LicenseVerdict::Unlicensed);
+        assert_eq!(
+            LicensePolicy::new().evaluate(Some("Apache-2.0")),
+            LicenseVerdict::Allowed
+        );
+    }
+
+    #[test]
+    fn listed_licenses_are_evaluated() {
+        let policy = LicensePolicy::new().deny("GPL-3.0");
+        let license: LicenseLicense = serde_json::from_str(
+            r#"{
+                "key": "gpl-3.0",
+                "name": "GNU General Public License v3.0",
+                "spdx_id": "GPL-3.0",
+                "url": "https://api.github.com/licenses/gpl-3.0",
+                "node_id": "MDc6TGljZW5zZTk="
+            }"#,
+        )
+        .unwrap();
+
+        let listed = RepositoryLicense::new("a".to_string(), Some(license), &policy);
+        assert_eq!(listed.spdx_id.as_deref(), Some("GPL-3.0"));
+        assert_eq!(
+            listed.license_name.as_deref(),
+            Some("GNU General Public License v3.0")
+        );
+        assert_eq!(listed.verdict, LicenseVerdict::Denied);
+
+        let unlicensed = RepositoryLicense::new("b".to_string(), None, &policy);
+        assert_eq!(unlicensed.spdx_id, None);
+        assert_eq!(unlicensed.verdict, LicenseVerdict::Unlicensed);
+    }
+
+    #[test]
+    fn report_is_grouped_by_license() {
+        let policy = LicensePolicy::new().deny("GPL-3.0");
+        let report = LicenseReport::new(
+            "acme",
+            &policy,
+            vec![
+                repository("b", Some("MIT"), &policy),
+                repository("c", None, &policy),
+                repository("a", Some("MIT"), &policy),
+                repository("d", Some("GPL-3.0"), &policy),
+            ],
+        );
+
+        assert_eq!(report.by_license["MIT"], vec!["a", "b"]);
+        assert_eq!(report.by_license[NO_LICENSE], vec!["c"]);
+        assert_eq!(report.violations().len(), 2);
+        assert_eq!(
+            report.to_csv_string(),
+            "spdx_id,license_name,repository,verdict\n\
+             GPL-3.0,\"The GPL-3.0 license, with, commas\",d,denied\n\
+             MIT,\"The MIT license, with, commas\",a,allowed\n\
+             MIT,\"The MIT license, with, commas\",b,allowed\n\
+             NONE,,c,unlicensed\n"
+        );
+    }
+}
+
+// endregion
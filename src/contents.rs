@@ -0,0 +1,289 @@
+use crate::endpoint::Endpoint;
+use crate::helpers::encode_file_path;
+use crate::impl_repository_scope;
+use crate::types::*;
+use crate::GitHubApi;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetContents {
+    pub owner: String,
+    pub repository: String,
+    pub path: String,
+    pub query: ContentsQuery,
+}
+
+impl Endpoint for GetContents {
+    type Response = Contents;
+    type Query = ContentsQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/contents/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.path)
+        )
+    }
+
+    fn query(&self) -> &ContentsQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetReadme {
+    pub owner: String,
+    pub repository: String,
+    pub query: ContentsQuery,
+}
+
+impl Endpoint for GetReadme {
+    type Response = ContentFile;
+    type Query = ContentsQuery;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/readme", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &ContentsQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateOrUpdateFile {
+    pub owner: String,
+    pub repository: String,
+    pub path: String,
+    pub update: FileUpdate,
+}
+
+impl Endpoint for CreateOrUpdateFile {
+    type Response = FileCommit;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/contents/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.path)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        let mut body = serde_json::to_value(&self.update).ok()?;
+        body["content"] = Value::String(base64::encode(&self.update.content));
+        Some(body)
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteFile {
+    pub owner: String,
+    pub repository: String,
+    pub path: String,
+    pub deletion: FileDeletion,
+}
+
+impl Endpoint for DeleteFile {
+    type Response = FileCommit;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/contents/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.path)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.deletion).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+/// Implement the contents endpoints.
+impl GitHubApi {
+    /// Gets a file, a directory listing, a symlink or a submodule.
+    /// `reference` is a commit, a branch or a tag, and defaults to the default branch.
+    pub fn get_contents(
+        &self,
+        owner: &str,
+        repository: &str,
+        path: &str,
+        reference: Option<&str>,
+    ) -> Response<Contents> {
+        self.get_endpoint(&GetContents {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            path: path.to_string(),
+            query: ContentsQuery {
+                r#ref: reference.map(str::to_string),
+            },
+        })
+    }
+
+    /// Gets the preferred README of a repository.
+    pub fn get_readme(
+        &self,
+        owner: &str,
+        repository: &str,
+        reference: Option<&str>,
+    ) -> Response<ContentFile> {
+        self.get_endpoint(&GetReadme {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            query: ContentsQuery {
+                r#ref: reference.map(str::to_string),
+            },
+        })
+    }
+
+    /// Creates a file, or replaces it if `update.sha` is the SHA of its current blob.
+    /// GitHub answers "409 Conflict" if the file changed in the meantime.
+    pub fn create_or_update_file(
+        &self,
+        owner: &str,
+        repository: &str,
+        path: &str,
+        update: &FileUpdate,
+    ) -> Response<FileCommit> {
+        self.get_endpoint(&CreateOrUpdateFile {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            path: path.to_string(),
+            update: update.clone(),
+        })
+    }
+
+    pub fn delete_file(
+        &self,
+        owner: &str,
+        repository: &str,
+        path: &str,
+        deletion: &FileDeletion,
+    ) -> Response<FileCommit> {
+        self.get_endpoint(&DeleteFile {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            path: path.to_string(),
+            deletion: deletion.clone(),
+        })
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parse_json;
+
+    const LINKS: &str = r#""_links": {
+        "self": "https://api.github.com/repos/a/b/contents/x",
+        "git": "https://api.github.com/repos/a/b/git/blobs/abc",
+        "html": "https://github.com/a/b/blob/main/x"
+    }"#;
+
+    #[test]
+    fn contents_are_told_apart() {
+        let file = format!(
+            r#"{{"type": "file", "encoding": "base64", "size": 5, "name": "x", "path": "x",
+                "content": "a2V5OiAx\n", "sha": "abc", "url": "", "git_url": null,
+                "html_url": null, "download_url": null, {}}}"#,
+            LINKS
+        );
+        let directory = format!(
+            r#"[{{"type": "dir", "size": 0, "name": "x", "path": "x", "sha": "abc", "url": "",
+                "git_url": null, "html_url": null, "download_url": null, {}}}]"#,
+            LINKS
+        );
+
+        match parse_json::<Contents>(&file).unwrap() {
+            Contents::File(file) => assert_eq!(file.decoded_content().unwrap(), "key: 1"),
+            other => panic!("Unexpected contents: {:?}", other),
+        }
+
+        match parse_json::<Contents>(&directory).unwrap() {
+            Contents::Directory(entries) => assert_eq!(entries[0].r#type, "dir"),
+            other => panic!("Unexpected contents: {:?}", other),
+        }
+
+        assert!(parse_json::<Contents>(r#"{"type": "unknown"}"#).is_err());
+    }
+
+    #[test]
+    fn submodules_can_lack_links() {
+        let submodule = r#"{
+            "type": "submodule",
+            "submodule_git_url": "https://github.com/a/c.git",
+            "size": 0, "name": "c", "path": "vendor/c", "sha": "abc",
+            "url": "https://api.github.com/repos/a/b/contents/vendor/c",
+            "git_url": null, "html_url": null, "download_url": null,
+            "_links": {
+                "self": "https://api.github.com/repos/a/b/contents/vendor/c",
+                "git": null,
+                "html": null
+            }
+        }"#;
+
+        match parse_json::<Contents>(submodule).unwrap() {
+            Contents::Submodule(submodule) => {
+                assert_eq!(submodule.links.git, None);
+                assert_eq!(submodule.links.html, None);
+            }
+            other => panic!("Unexpected contents: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn file_updates_are_base64_encoded() {
+        let endpoint = CreateOrUpdateFile {
+            owner: "a".to_string(),
+            repository: "b".to_string(),
+            path: "/config/ci settings.yml".to_string(),
+            update: FileUpdate {
+                message: "Update CI".to_string(),
+                content: "key: 1".to_string(),
+                sha: Some("abc".to_string()),
+                ..FileUpdate::default()
+            },
+        };
+
+        assert_eq!(
+            endpoint.path(),
+            "repos/a/b/contents/config/ci%20settings.yml"
+        );
+        assert_eq!(
+            endpoint.body().unwrap(),
+            serde_json::json!({"message": "Update CI", "content": "a2V5OiAx", "sha": "abc"})
+        );
+    }
+}
+
+// endregion
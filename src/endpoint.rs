@@ -0,0 +1,104 @@
+use crate::helpers::UncapturedFields;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// How an endpoint hands out its pages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaginationKind {
+    /// A single response.
+    None,
+
+    /// Numbered pages, with a "last" link that allows getting them concurrently.
+    Pages,
+
+    /// Pages that can only be reached by following the "next" link,
+    /// such as the ones using `after=` and `before=`.
+    Cursor,
+}
+
+/// Which of GitHub's rate limits an endpoint counts against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitBucket {
+    Core,
+
+    /// The search endpoints have a smaller limit of their own.
+    /// Requests to them wait for it to reset once it runs out.
+    Search,
+}
+
+/// Implements `Endpoint::owner` and `Endpoint::repository`
+/// for endpoints that have `owner` and `repository` fields.
+#[macro_export]
+macro_rules! impl_repository_scope {
+    () => {
+        fn owner(&self) -> Option<&str> {
+            Some(&self.owner)
+        }
+
+        fn repository(&self) -> Option<&str> {
+            Some(&self.repository)
+        }
+    };
+}
+
+/// Describes an endpoint. Implement this to add a new one,
+/// then call it with `GitHubApi::get_endpoint` or `GitHubApi::paginate`.
+///
+/// ```ignore
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// pub struct ListReleaseAssets {
+///     pub owner: String,
+///     pub repository: String,
+///     pub release_id: u64,
+/// }
+///
+/// impl Endpoint for ListReleaseAssets {
+///     type Response = Vec<ReleasesAsset>;
+///     type Query = NoQuery;
+///
+///     const PAGINATION: PaginationKind = PaginationKind::Pages;
+///
+///     fn path(&self) -> String {
+///         format!("repos/{}/{}/releases/{}/assets", self.owner, self.repository, self.release_id)
+///     }
+///
+///     fn query(&self) -> &NoQuery {
+///         &NoQuery {}
+///     }
+/// }
+/// ```
+pub trait Endpoint {
+    /// What a single request decodes into. For paginated endpoints, this is a whole page.
+    type Response: DeserializeOwned + UncapturedFields;
+
+    /// The query parameters, which are URL encoded into the request.
+    /// Use `NoQuery` if there aren't any.
+    type Query: Serialize;
+
+    const METHOD: Method = Method::GET;
+    const PAGINATION: PaginationKind = PaginationKind::None;
+    const RATE_LIMIT: RateLimitBucket = RateLimitBucket::Core;
+
+    /// The path template, relative to the root of the API, with the parameters filled in.
+    /// For example "repos/{owner}/{repo}/releases/{id}/assets".
+    fn path(&self) -> String;
+
+    fn query(&self) -> &Self::Query;
+
+    /// The JSON body to send, if any.
+    fn body(&self) -> Option<Value> {
+        None
+    }
+
+    /// Ends up in `GitHubApiResult.owner`.
+    fn owner(&self) -> Option<&str> {
+        None
+    }
+
+    /// Ends up in `GitHubApiResult.repository`.
+    fn repository(&self) -> Option<&str> {
+        None
+    }
+}
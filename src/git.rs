@@ -0,0 +1,1002 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::helpers::encode_file_path;
+use crate::impl_repository_scope;
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBlob {
+    pub owner: String,
+    pub repository: String,
+    pub blob: NewBlob,
+}
+
+impl Endpoint for CreateBlob {
+    type Response = BlobReference;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/git/blobs", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.blob).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBlob {
+    pub owner: String,
+    pub repository: String,
+    pub sha: String,
+}
+
+impl Endpoint for GetBlob {
+    type Response = Blob;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/git/blobs/{}",
+            self.owner, self.repository, self.sha
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+/// Gets a tree by SHA, or by the name of a branch or a tag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTree {
+    pub owner: String,
+    pub repository: String,
+    pub reference: String,
+    pub query: TreeQuery,
+}
+
+impl Endpoint for GetTree {
+    type Response = Tree;
+    type Query = TreeQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/git/trees/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.reference)
+        )
+    }
+
+    fn query(&self) -> &TreeQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTree {
+    pub owner: String,
+    pub repository: String,
+    pub tree: NewTree,
+}
+
+impl Endpoint for CreateTree {
+    type Response = Tree;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/git/trees", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.tree).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetGitCommit {
+    pub owner: String,
+    pub repository: String,
+    pub sha: String,
+}
+
+impl Endpoint for GetGitCommit {
+    type Response = GitCommit;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/git/commits/{}",
+            self.owner, self.repository, self.sha
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateGitCommit {
+    pub owner: String,
+    pub repository: String,
+    pub commit: NewGitCommit,
+}
+
+impl Endpoint for CreateGitCommit {
+    type Response = GitCommit;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/git/commits", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.commit).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+/// Lists the references whose names start with `prefix`, such as "heads/release-".
+/// An empty prefix lists all of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListMatchingRefs {
+    pub owner: String,
+    pub repository: String,
+    pub prefix: String,
+}
+
+impl Endpoint for ListMatchingRefs {
+    type Response = Vec<GitRef>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/git/matching-refs/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.prefix)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+/// Gets a reference by its name without "refs/", such as "heads/main" or "tags/v1.0.0".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRef {
+    pub owner: String,
+    pub repository: String,
+    pub reference: String,
+}
+
+impl Endpoint for GetRef {
+    type Response = GitRef;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/git/ref/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.reference)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateRef {
+    pub owner: String,
+    pub repository: String,
+    pub new_ref: NewRef,
+}
+
+impl Endpoint for CreateRef {
+    type Response = GitRef;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/git/refs", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.new_ref).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRef {
+    pub owner: String,
+    pub repository: String,
+    pub reference: String,
+    pub update: RefUpdate,
+}
+
+impl Endpoint for UpdateRef {
+    type Response = GitRef;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PATCH;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/git/refs/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.reference)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.update).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteRef {
+    pub owner: String,
+    pub repository: String,
+    pub reference: String,
+}
+
+impl Endpoint for DeleteRef {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/git/refs/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.reference)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+/// Creates an annotated tag object.
+/// It only becomes visible as a tag once a "refs/tags/" reference points to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTag {
+    pub owner: String,
+    pub repository: String,
+    pub tag: NewTag,
+}
+
+impl Endpoint for CreateTag {
+    type Response = GitTag;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/git/tags", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.tag).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTag {
+    pub owner: String,
+    pub repository: String,
+    pub sha: String,
+}
+
+impl Endpoint for GetTag {
+    type Response = GitTag;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/git/tags/{}",
+            self.owner, self.repository, self.sha
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+pub type RefPaginator<'a> = Paginator<'a, ListMatchingRefs>;
+
+/// Implement the git database endpoints.
+impl GitHubApi {
+    pub fn create_blob(
+        &self,
+        owner: &str,
+        repository: &str,
+        blob: &NewBlob,
+    ) -> Response<BlobReference> {
+        self.get_endpoint(&CreateBlob {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            blob: blob.clone(),
+        })
+    }
+
+    pub fn get_blob(&self, owner: &str, repository: &str, sha: &str) -> Response<Blob> {
+        self.get_endpoint(&GetBlob {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            sha: sha.to_string(),
+        })
+    }
+
+    /// Gets a tree, and with `recursive` the entries of all of its subtrees as well.
+    pub fn get_tree(
+        &self,
+        owner: &str,
+        repository: &str,
+        reference: &str,
+        recursive: bool,
+    ) -> Response<Tree> {
+        self.get_endpoint(&GetTree {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            reference: reference.to_string(),
+            query: TreeQuery {
+                recursive: if recursive { Some(true) } else { None },
+            },
+        })
+    }
+
+    pub fn create_tree(&self, owner: &str, repository: &str, tree: &NewTree) -> Response<Tree> {
+        self.get_endpoint(&CreateTree {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            tree: tree.clone(),
+        })
+    }
+
+    pub fn get_git_commit(&self, owner: &str, repository: &str, sha: &str) -> Response<GitCommit> {
+        self.get_endpoint(&GetGitCommit {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            sha: sha.to_string(),
+        })
+    }
+
+    pub fn create_git_commit(
+        &self,
+        owner: &str,
+        repository: &str,
+        commit: &NewGitCommit,
+    ) -> Response<GitCommit> {
+        self.get_endpoint(&CreateGitCommit {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            commit: commit.clone(),
+        })
+    }
+
+    /// Creates a paginator over the references whose names start with `prefix`.
+    pub fn list_matching_refs(
+        &self,
+        owner: &str,
+        repository: &str,
+        prefix: &str,
+    ) -> RefPaginator<'_> {
+        self.paginate(ListMatchingRefs {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            prefix: prefix.to_string(),
+        })
+    }
+
+    pub fn get_ref(&self, owner: &str, repository: &str, reference: &str) -> Response<GitRef> {
+        self.get_endpoint(&GetRef {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            reference: reference.to_string(),
+        })
+    }
+
+    /// Creates a reference. Its name has to be complete, such as "refs/heads/main".
+    pub fn create_ref(
+        &self,
+        owner: &str,
+        repository: &str,
+        reference: &str,
+        sha: &str,
+    ) -> Response<GitRef> {
+        self.get_endpoint(&CreateRef {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            new_ref: NewRef {
+                r#ref: reference.to_string(),
+                sha: sha.to_string(),
+            },
+        })
+    }
+
+    /// Points a reference, such as "heads/main", to another commit.
+    /// Without `force`, GitHub refuses anything but a fast-forward.
+    pub fn update_ref(
+        &self,
+        owner: &str,
+        repository: &str,
+        reference: &str,
+        sha: &str,
+        force: bool,
+    ) -> Response<GitRef> {
+        self.get_endpoint(&UpdateRef {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            reference: reference.to_string(),
+            update: RefUpdate {
+                sha: sha.to_string(),
+                force,
+            },
+        })
+    }
+
+    pub fn delete_ref(&self, owner: &str, repository: &str, reference: &str) -> Response<()> {
+        self.get_endpoint(&DeleteRef {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            reference: reference.to_string(),
+        })
+    }
+
+    pub fn create_tag(&self, owner: &str, repository: &str, tag: &NewTag) -> Response<GitTag> {
+        self.get_endpoint(&CreateTag {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            tag: tag.clone(),
+        })
+    }
+
+    pub fn get_tag(&self, owner: &str, repository: &str, sha: &str) -> Response<GitTag> {
+        self.get_endpoint(&GetTag {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            sha: sha.to_string(),
+        })
+    }
+}
+
+// region Commit builder
+
+/// A change staged on a `CommitBuilder`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileChange {
+    /// Adds or replaces a file. Without a mode, existing files, executables and links keep theirs,
+    /// and everything else becomes a plain file.
+    Write {
+        content: Vec<u8>,
+        mode: Option<FileMode>,
+    },
+    Delete,
+    /// Changes the mode of an existing file, without touching its content.
+    Mode(FileMode),
+}
+
+/// Stages changes to the files of a branch in memory, and commits them all at once.
+/// Create one with `GitHubApi::commit_builder`.
+pub struct CommitBuilder<'a> {
+    github_api: &'a GitHubApi,
+    owner: String,
+    repository: String,
+    branch: String,
+    changes: BTreeMap<String, FileChange>,
+    author: Option<CommitSignature>,
+    committer: Option<CommitSignature>,
+    attempts: u32,
+}
+
+impl<'a> CommitBuilder<'a> {
+    pub fn new(github_api: &'a GitHubApi, owner: &str, repository: &str, branch: &str) -> Self {
+        Self {
+            github_api,
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            branch: branch.to_string(),
+            changes: BTreeMap::new(),
+            author: None,
+            committer: None,
+            attempts: 3,
+        }
+    }
+
+    /// Stages a change. A later change to the same path replaces the earlier one.
+    pub fn change(mut self, path: &str, change: FileChange) -> Self {
+        self.changes
+            .insert(path.trim_matches('/').to_string(), change);
+        self
+    }
+
+    pub fn write(self, path: &str, content: &str) -> Self {
+        self.write_bytes(path, content.as_bytes())
+    }
+
+    pub fn write_bytes(self, path: &str, content: &[u8]) -> Self {
+        self.change(
+            path,
+            FileChange::Write {
+                content: content.to_vec(),
+                mode: None,
+            },
+        )
+    }
+
+    pub fn write_with_mode(self, path: &str, content: &[u8], mode: FileMode) -> Self {
+        self.change(
+            path,
+            FileChange::Write {
+                content: content.to_vec(),
+                mode: Some(mode),
+            },
+        )
+    }
+
+    pub fn delete(self, path: &str) -> Self {
+        self.change(path, FileChange::Delete)
+    }
+
+    pub fn set_mode(self, path: &str, mode: FileMode) -> Self {
+        self.change(path, FileChange::Mode(mode))
+    }
+
+    /// Both default to the authenticated user.
+    pub fn author(mut self, author: CommitSignature) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    pub fn committer(mut self, committer: CommitSignature) -> Self {
+        self.committer = Some(committer);
+        self
+    }
+
+    /// How many times to start over when the branch moves while committing. Defaults to 3.
+    pub fn attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    pub fn changes(&self) -> &BTreeMap<String, FileChange> {
+        &self.changes
+    }
+
+    /// Creates the blobs, the tree and the commit, and fast-forwards the branch to it.
+    ///
+    /// When someone else pushed to the branch in the meantime,
+    /// the changes are applied on top of their commit instead.
+    pub fn commit(&self, message: &str) -> Response<GitCommit> {
+        if self.changes.is_empty() {
+            return Err(GitHubApiError::InvalidChange(
+                "There is nothing to commit.".to_string(),
+            ));
+        }
+
+        let (owner, repository) = (self.owner.as_str(), self.repository.as_str());
+        let reference = format!("heads/{}", self.branch);
+        let blobs = self.create_blobs()?;
+        let mut attempt = 1;
+
+        loop {
+            let head = self
+                .github_api
+                .get_ref(owner, repository, &reference)?
+                .result;
+            let parent = self
+                .github_api
+                .get_git_commit(owner, repository, &head.object.sha)?
+                .result;
+
+            let mut lookup = TreeLookup::new(self, &parent.tree.sha);
+            let tree = NewTree {
+                base_tree: Some(parent.tree.sha.clone()),
+                tree: tree_entries(&self.changes, &blobs, |path| lookup.entry(path))?,
+            };
+            let tree = self
+                .github_api
+                .create_tree(owner, repository, &tree)?
+                .result;
+
+            let commit = NewGitCommit {
+                message: message.to_string(),
+                tree: tree.sha,
+                parents: vec![parent.sha.clone()],
+                author: self.author.clone(),
+                committer: self.committer.clone(),
+                signature: None,
+            };
+            let commit = self
+                .github_api
+                .create_git_commit(owner, repository, &commit)?;
+
+            let error = match self.github_api.update_ref(
+                owner,
+                repository,
+                &reference,
+                &commit.result.sha,
+                false,
+            ) {
+                Ok(_) => return Ok(commit),
+                Err(error) => error,
+            };
+
+            // Only start over when the update failed because someone else moved the branch.
+            let moved = match self.github_api.get_ref(owner, repository, &reference) {
+                Ok(current) => current.result.object.sha != parent.sha,
+                Err(_) => false,
+            };
+
+            if !moved || attempt >= self.attempts {
+                return Err(error);
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Stores the content of every write, which only has to happen once.
+    /// Returns the SHAs of the blobs by path.
+    fn create_blobs(&self) -> Result<HashMap<String, String>, GitHubApiError> {
+        let mut blobs = HashMap::new();
+
+        for (path, change) in &self.changes {
+            if let FileChange::Write { content, .. } = change {
+                let blob = match std::str::from_utf8(content) {
+                    Ok(text) => NewBlob::text(text),
+                    Err(_) => NewBlob::bytes(content),
+                };
+                let blob = self
+                    .github_api
+                    .create_blob(&self.owner, &self.repository, &blob)?;
+                blobs.insert(path.clone(), blob.result.sha);
+            }
+        }
+
+        Ok(blobs)
+    }
+}
+
+/// Finds entries of a tree by path, getting only the directories on the way to them.
+struct TreeLookup<'a> {
+    builder: &'a CommitBuilder<'a>,
+
+    /// The trees that were gotten so far, by the path of their directory.
+    trees: HashMap<String, Tree>,
+
+    /// The SHAs of the trees, by the path of their directory.
+    shas: HashMap<String, String>,
+}
+
+impl<'a> TreeLookup<'a> {
+    fn new(builder: &'a CommitBuilder<'a>, root: &str) -> Self {
+        let mut shas = HashMap::new();
+        shas.insert(String::new(), root.to_string());
+
+        Self {
+            builder,
+            trees: HashMap::new(),
+            shas,
+        }
+    }
+
+    /// Gets the mode and the SHA of the entry at `path`, if there is one.
+    fn entry(&mut self, path: &str) -> Result<Option<(String, String)>, GitHubApiError> {
+        let mut directory = String::new();
+        let mut parts = path.split('/').peekable();
+
+        while let Some(name) = parts.next() {
+            let tree = match self.tree(&directory)? {
+                Some(tree) => tree,
+                None => return Ok(None),
+            };
+            let entry = match tree.tree.iter().find(|it| it.path == name) {
+                Some(entry) => entry,
+                None if tree.truncated => {
+                    return Err(GitHubApiError::InvalidChange(format!(
+                        "The tree of {} is too large to tell whether {} exists.",
+                        directory, path
+                    )))
+                }
+                None => return Ok(None),
+            };
+
+            if parts.peek().is_none() {
+                return Ok(Some((entry.mode.clone(), entry.sha.clone())));
+            }
+
+            if entry.r#type != GitObjectType::Tree {
+                return Ok(None);
+            }
+
+            let sha = entry.sha.clone();
+            directory = if directory.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/{}", directory, name)
+            };
+            self.shas.insert(directory.clone(), sha);
+        }
+
+        Ok(None)
+    }
+
+    fn tree(&mut self, directory: &str) -> Result<Option<&Tree>, GitHubApiError> {
+        let sha = match self.shas.get(directory) {
+            Some(sha) => sha.clone(),
+            None => return Ok(None),
+        };
+
+        if !self.trees.contains_key(directory) {
+            let builder = self.builder;
+            let tree = builder
+                .github_api
+                .get_tree(&builder.owner, &builder.repository, &sha, false)?
+                .result;
+            self.trees.insert(directory.to_string(), tree);
+        }
+
+        Ok(self.trees.get(directory))
+    }
+}
+
+/// Turns the staged changes into entries for a new tree.
+/// `existing` gets the mode and the SHA of an entry of the base tree,
+/// and is only called for the changes that depend on them.
+fn tree_entries<F>(
+    changes: &BTreeMap<String, FileChange>,
+    blobs: &HashMap<String, String>,
+    mut existing: F,
+) -> Result<Vec<NewTreeEntry>, GitHubApiError>
+where
+    F: FnMut(&str) -> Result<Option<(String, String)>, GitHubApiError>,
+{
+    let mut entries = Vec::new();
+
+    for (path, change) in changes {
+        let entry = match change {
+            FileChange::Write {
+                mode: Some(mode), ..
+            } => NewTreeEntry::blob(path, *mode, &blobs[path]),
+            FileChange::Write { mode: None, .. } => {
+                // Files, executables and links keep their mode,
+                // anything else (or nothing) becomes a plain file.
+                let mode = match existing(path)?.map(|(mode, _)| mode).as_deref() {
+                    Some("100755") => FileMode::Executable,
+                    Some("120000") => FileMode::Symlink,
+                    _ => FileMode::File,
+                };
+                NewTreeEntry::blob(path, mode, &blobs[path])
+            }
+            FileChange::Delete => NewTreeEntry::delete(path),
+            FileChange::Mode(mode) => match existing(path)? {
+                Some((_, sha)) => NewTreeEntry::blob(path, *mode, &sha),
+                None => {
+                    return Err(GitHubApiError::InvalidChange(format!(
+                        "{} doesn't exist.",
+                        path
+                    )))
+                }
+            },
+        };
+
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Implement the commit builder.
+impl GitHubApi {
+    /// Starts staging changes to the files of `branch`.
+    pub fn commit_builder(&self, owner: &str, repository: &str, branch: &str) -> CommitBuilder<'_> {
+        CommitBuilder::new(self, owner, repository, branch)
+    }
+}
+
+// endregion
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn tree_entries_can_remove_files() {
+        let tree = NewTree {
+            base_tree: Some("abc".to_string()),
+            tree: vec![
+                NewTreeEntry::text("Cargo.toml", FileMode::File, "[package]"),
+                NewTreeEntry::blob("run.sh", FileMode::Executable, "def"),
+                NewTreeEntry::delete("old.txt"),
+            ],
+        };
+
+        assert_eq!(
+            serde_json::to_value(&tree).unwrap(),
+            json!({
+                "base_tree": "abc",
+                "tree": [
+                    {"path": "Cargo.toml", "mode": "100644", "type": "blob", "content": "[package]"},
+                    {"path": "run.sh", "mode": "100755", "type": "blob", "sha": "def"},
+                    {"path": "old.txt", "mode": "100644", "type": "blob", "sha": null}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn staged_changes_keep_existing_modes() {
+        let mut base_tree = HashMap::new();
+        base_tree.insert("build.sh", ("100755", "1"));
+        base_tree.insert("old.txt", ("100644", "2"));
+        base_tree.insert("run.sh", ("100644", "3"));
+        base_tree.insert("latest", ("120000", "6"));
+        base_tree.insert("legacy.txt", ("100664", "7"));
+        let existing = |path: &str| {
+            Ok(base_tree
+                .get(path)
+                .map(|(mode, sha)| (mode.to_string(), sha.to_string())))
+        };
+
+        let write = |content: &str| FileChange::Write {
+            content: content.as_bytes().to_vec(),
+            mode: None,
+        };
+        let mut changes = BTreeMap::new();
+        changes.insert("build.sh".to_string(), write("make"));
+        changes.insert("VERSION".to_string(), write("1.2.3"));
+        changes.insert("latest".to_string(), write("v2"));
+        changes.insert("legacy.txt".to_string(), write("text"));
+        changes.insert("old.txt".to_string(), FileChange::Delete);
+        changes.insert("run.sh".to_string(), FileChange::Mode(FileMode::Executable));
+
+        let mut blobs = HashMap::new();
+        blobs.insert("build.sh".to_string(), "4".to_string());
+        blobs.insert("VERSION".to_string(), "5".to_string());
+        blobs.insert("latest".to_string(), "8".to_string());
+        blobs.insert("legacy.txt".to_string(), "9".to_string());
+
+        let entries = tree_entries(&changes, &blobs, existing).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&entries).unwrap(),
+            json!([
+                {"path": "VERSION", "mode": "100644", "type": "blob", "sha": "5"},
+                {"path": "build.sh", "mode": "100755", "type": "blob", "sha": "4"},
+                {"path": "latest", "mode": "120000", "type": "blob", "sha": "8"},
+                {"path": "legacy.txt", "mode": "100644", "type": "blob", "sha": "9"},
+                {"path": "old.txt", "mode": "100644", "type": "blob", "sha": null},
+                {"path": "run.sh", "mode": "100755", "type": "blob", "sha": "3"}
+            ])
+        );
+
+        changes.insert("missing.sh".to_string(), FileChange::Mode(FileMode::File));
+        assert!(tree_entries(&changes, &blobs, existing).is_err());
+    }
+
+    #[test]
+    fn explicit_modes_do_not_look_at_the_base_tree() {
+        let mut changes = BTreeMap::new();
+        changes.insert(
+            "VERSION".to_string(),
+            FileChange::Write {
+                content: b"1.2.3".to_vec(),
+                mode: Some(FileMode::File),
+            },
+        );
+        changes.insert("old.txt".to_string(), FileChange::Delete);
+
+        let mut blobs = HashMap::new();
+        blobs.insert("VERSION".to_string(), "5".to_string());
+
+        let entries = tree_entries(&changes, &blobs, |path| {
+            panic!("Looked up {}", path);
+        })
+        .unwrap();
+
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn trees_are_decoded() {
+        let text = r#"{
+            "sha": "abc",
+            "url": "https://api.github.com/repos/a/b/git/trees/abc",
+            "truncated": false,
+            "tree": [
+                {"path": "src", "mode": "040000", "type": "tree", "sha": "def", "url": "https://api.github.com/repos/a/b/git/trees/def"},
+                {"path": "src/lib.rs", "mode": "100644", "type": "blob", "sha": "123", "size": 42, "url": "https://api.github.com/repos/a/b/git/blobs/123"},
+                {"path": "vendor", "mode": "160000", "type": "commit", "sha": "456"},
+                {"path": "legacy.txt", "mode": "100664", "type": "blob", "sha": "789", "size": 1}
+            ]
+        }"#;
+
+        let tree: Tree = serde_json::from_str(text).unwrap();
+
+        assert_eq!(tree.tree[0].file_mode(), Some(FileMode::Directory));
+        assert_eq!(tree.tree[3].file_mode(), None);
+        assert_eq!(tree.tree[1].size, Some(42));
+        assert_eq!(tree.tree[2].r#type, GitObjectType::Commit);
+    }
+}
+
+// endregion
@@ -1,27 +1,30 @@
-use crate::types::{GitHubApiError, LimitRemainingReset};
+use crate::types::{
+    GitHubApiError, ItemFailure, LenientPage, LimitRemainingReset, PageLink, SearchResults,
+};
 use crate::Pagination;
 use lazy_static::lazy_static;
 use regex::Regex;
 use reqwest::header::{HeaderMap, HeaderValue};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::error::Error as JsonError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
 
 pub trait HeaderMapExtensions {
     fn get_as_u64(&self, key: &str) -> Option<u64>;
     fn get_rate_limits(&self) -> Option<LimitRemainingReset>;
     fn get_pagination(&self) -> Option<Vec<Pagination>>;
-    fn get_next_page(&self) -> Option<u64>;
-    fn is_ok(&self) -> bool;
+    fn get_next_link(&self) -> Option<PageLink>;
+    fn get_last_link(&self) -> Option<PageLink>;
 }
 
 impl HeaderMapExtensions for HeaderMap<HeaderValue> {
     fn get_as_u64(&self, key: &str) -> Option<u64> {
         match self.get(key) {
             Some(header_value) => match header_value.to_str() {
-                Ok(string_value) => match string_value.parse() {
-                    Ok(value) => Some(value),
-                    _ => None,
-                },
+                Ok(string_value) => string_value.parse().ok(),
                 _ => None,
             },
             _ => None,
@@ -37,63 +40,46 @@ impl HeaderMapExtensions for HeaderMap<HeaderValue> {
             limit,
             remaining,
             reset,
+            uncaptured: HashMap::new(),
         })
     }
 
     fn get_pagination(&self) -> Option<Vec<Pagination>> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r#"<.*?[\?&]page=(\d+).*?>; rel="(.*?)""#).unwrap();
+            static ref RE: Regex = Regex::new(r#"<([^>]*)>\s*;\s*rel="([^"]*)""#).unwrap();
         }
 
-        match self.get("Link") {
-            None => None,
-            Some(header) => match header.to_str() {
-                Ok(data) => Some(
-                    RE.captures_iter(data)
-                        .map(|it| {
-                            (
-                                it.get(2).unwrap().as_str(),
-                                it.get(1).unwrap().as_str().parse::<u64>().unwrap(),
-                            )
-                        })
-                        .map(|(direction, number)| match direction {
-                            "first" => Pagination::First(number),
-                            "prev" => Pagination::Previous(number),
-                            "next" => Pagination::Next(number),
-                            "last" => Pagination::Last(number),
-                            other => Pagination::Undefined(other.to_string(), number),
-                        })
-                        .collect::<Vec<_>>(),
-                ),
-                _ => None,
-            },
-        }
-    }
+        let data = self.get("Link")?.to_str().ok()?;
 
-    fn get_next_page(&self) -> Option<u64> {
-        let result = self.get_pagination()?.into_iter().find(|it| match it {
-            Pagination::Next(_) => true,
-            _ => false,
-        })?;
+        Some(
+            RE.captures_iter(data)
+                .filter_map(|it| {
+                    let link = PageLink::parse(it.get(1)?.as_str())?;
 
-        if let Pagination::Next(value) = result {
-            Some(value)
-        } else {
-            None
-        }
+                    Some(match it.get(2)?.as_str() {
+                        "first" => Pagination::First(link),
+                        "prev" => Pagination::Previous(link),
+                        "next" => Pagination::Next(link),
+                        "last" => Pagination::Last(link),
+                        other => Pagination::Undefined(other.to_string(), link),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )
     }
 
-    fn is_ok(&self) -> bool {
-        match self.get("status") {
-            Some(value) => match value.to_str() {
-                Ok(value) => match value.split_whitespace().next() {
-                    Some(value) => value == "200",
-                    _ => false,
-                },
-                _ => false,
-            },
-            _ => false,
-        }
+    fn get_next_link(&self) -> Option<PageLink> {
+        self.get_pagination()?.into_iter().find_map(|it| match it {
+            Pagination::Next(link) => Some(link),
+            _ => None,
+        })
+    }
+
+    fn get_last_link(&self) -> Option<PageLink> {
+        self.get_pagination()?.into_iter().find_map(|it| match it {
+            Pagination::Last(link) => Some(link),
+            _ => None,
+        })
     }
 }
 
@@ -101,12 +87,318 @@ pub fn parse_json<'a, T>(text: &'a str) -> Result<T, GitHubApiError>
 where
     T: Deserialize<'a>,
 {
-    match serde_json::from_str(&text) {
+    match serde_json::from_str(text) {
         Ok(value) => Ok(value),
         Err(error) => Err(GitHubApiError::JsonError((error, text.to_string()))),
     }
 }
 
+/// Parses a JSON array one element at a time.
+/// Only fails if the text isn't an array at all.
+pub fn parse_json_lenient<T>(text: &str) -> Result<LenientPage<T>, GitHubApiError>
+where
+    T: DeserializeOwned,
+{
+    let elements: Vec<Value> = parse_json(text)?;
+    let mut items = Vec::with_capacity(elements.len());
+    let mut failures = Vec::new();
+
+    for (index, raw) in elements.into_iter().enumerate() {
+        match T::deserialize(&raw) {
+            Ok(item) => items.push(item),
+            Err(error) => failures.push(ItemFailure { index, raw, error }),
+        }
+    }
+
+    Ok(LenientPage { items, failures })
+}
+
+/// Decodes file content the way GitHub encoded it.
+pub fn decode_content(content: &str, encoding: &str) -> Result<String, GitHubApiError> {
+    match encoding {
+        "utf-8" | "utf8" => Ok(content.to_string()),
+        _ => String::from_utf8(decode_bytes(content, encoding)?)
+            .map_err(|error| GitHubApiError::DecodeError(error.to_string())),
+    }
+}
+
+pub fn decode_bytes(content: &str, encoding: &str) -> Result<Vec<u8>, GitHubApiError> {
+    match encoding {
+        "base64" => {
+            // GitHub wraps the base64 content in lines of 60 characters.
+            let stripped: String = content.split_whitespace().collect();
+            base64::decode(&stripped)
+                .map_err(|error| GitHubApiError::DecodeError(error.to_string()))
+        }
+        "utf-8" | "utf8" => Ok(content.as_bytes().to_vec()),
+        other => Err(GitHubApiError::DecodeError(format!(
+            "Unsupported encoding: {}",
+            other
+        ))),
+    }
+}
+
 pub trait ToJsonString {
     fn to_json_string(&self) -> Result<String, JsonError>;
 }
+
+/// Surfaces the fields that ended up in the `uncaptured` maps,
+/// which is to say the fields GitHub returned that the models don't know about.
+pub trait UncapturedFields {
+    /// Appends the path of every uncaptured field to `paths`.
+    /// Items in arrays are written as `[]`, so that one path covers the whole array.
+    fn collect_uncaptured(&self, prefix: &str, paths: &mut Vec<String>);
+
+    /// Gets the sorted and deduplicated paths of every uncaptured field.
+    fn uncaptured_fields(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        self.collect_uncaptured("", &mut paths);
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+impl<T> UncapturedFields for Vec<T>
+where
+    T: UncapturedFields,
+{
+    fn collect_uncaptured(&self, prefix: &str, paths: &mut Vec<String>) {
+        let prefix = format!("{}[]", prefix);
+        for item in self {
+            item.collect_uncaptured(&prefix, paths);
+        }
+    }
+}
+
+impl<T> UncapturedFields for Option<T>
+where
+    T: UncapturedFields,
+{
+    fn collect_uncaptured(&self, prefix: &str, paths: &mut Vec<String>) {
+        if let Some(value) = self {
+            value.collect_uncaptured(prefix, paths);
+        }
+    }
+}
+
+/// Raw JSON has no model, so nothing in it counts as uncaptured.
+impl UncapturedFields for Value {
+    fn collect_uncaptured(&self, _prefix: &str, _paths: &mut Vec<String>) {}
+}
+
+impl UncapturedFields for () {
+    fn collect_uncaptured(&self, _prefix: &str, _paths: &mut Vec<String>) {}
+}
+
+impl<T> UncapturedFields for LenientPage<T>
+where
+    T: UncapturedFields,
+{
+    fn collect_uncaptured(&self, prefix: &str, paths: &mut Vec<String>) {
+        self.items.collect_uncaptured(prefix, paths);
+    }
+}
+
+impl<T> UncapturedFields for SearchResults<T>
+where
+    T: UncapturedFields,
+{
+    fn collect_uncaptured(&self, prefix: &str, paths: &mut Vec<String>) {
+        for key in self.uncaptured.keys() {
+            paths.push(join_path(prefix, key));
+        }
+
+        self.items.iter().for_each(|it| {
+            it.item
+                .collect_uncaptured(&join_path(prefix, "items[]"), paths)
+        });
+    }
+}
+
+/// How long to wait before the next request, given what is left of a rate limit,
+/// and the time it resets at, in seconds since the epoch.
+pub fn rate_limit_delay(remaining: u64, reset: u64, now: u64) -> Option<Duration> {
+    if remaining > 0 || reset < now {
+        return None;
+    }
+
+    // Wait a second longer, in case the clocks are a little apart.
+    Some(Duration::from_secs(reset - now + 1))
+}
+
+/// Percent-encodes a value, such as a label name, so that it can be a single segment of a path.
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+/// Encodes every segment of a path, but keeps the slashes between them.
+pub fn encode_file_path(path: &str) -> String {
+    path.trim_matches('/')
+        .split('/')
+        .map(encode_path_segment)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub fn join_path(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{TagsCommit, TagsResponse};
+
+    #[test]
+    fn lenient_parsing_keeps_good_items() {
+        let text = r#"[
+            {"sha": "a", "url": "https://example.com/a"},
+            {"sha": 1},
+            {"sha": "c", "url": "https://example.com/c"}
+        ]"#;
+
+        let page: LenientPage<TagsCommit> = parse_json_lenient(text).unwrap();
+
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.items[1].sha, "c");
+        assert_eq!(page.failures.len(), 1);
+        assert_eq!(page.failures[0].index, 1);
+        assert_eq!(page.failures[0].raw["sha"], 1);
+    }
+
+    #[test]
+    fn lenient_parsing_rejects_non_arrays() {
+        let result: Result<LenientPage<TagsCommit>, _> = parse_json_lenient(r#"{"sha": "a"}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn pagination_links_are_kept_verbatim() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Link",
+            HeaderValue::from_static(
+                "<https://api.github.com/repositories/1/tags?per_page=100&page=2>; rel=\"next\", \
+                 <https://api.github.com/repositories/1/tags?per_page=100&page=7>; rel=\"last\"",
+            ),
+        );
+
+        let next = headers.get_next_link().unwrap();
+        assert_eq!(
+            next.url,
+            "https://api.github.com/repositories/1/tags?per_page=100&page=2"
+        );
+        assert_eq!(next.page(), Some(2));
+
+        assert_eq!(headers.get_last_link().unwrap().page(), Some(7));
+    }
+
+    #[test]
+    fn cursor_pagination_links_are_followed() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Link",
+            HeaderValue::from_static(
+                "<https://api.github.com/orgs/acme/audit-log?after=MS4y%3D&before=>; rel=\"next\"",
+            ),
+        );
+
+        let next = headers.get_next_link().unwrap();
+        assert_eq!(next.page(), None);
+        assert_eq!(next.query["after"], "MS4y=");
+    }
+
+    #[test]
+    fn malformed_pagination_links_do_not_panic() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Link",
+            HeaderValue::from_static(
+                "<not a url?page=x>; rel=\"next\", <https://api.github.com/a?page=99999999999999999999>; rel=\"last\", garbage",
+            ),
+        );
+
+        let pagination = headers.get_pagination().unwrap();
+        assert_eq!(pagination.len(), 1);
+        assert_eq!(headers.get_next_link(), None);
+
+        match &pagination[0] {
+            Pagination::Last(link) => assert_eq!(link.page(), None),
+            other => panic!("Unexpected link: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn base64_content_is_decoded() {
+        let content = "TUlUIExp\nY2Vuc2U=\n";
+        assert_eq!(decode_content(content, "base64").unwrap(), "MIT License");
+        assert_eq!(decode_content("plain", "utf-8").unwrap(), "plain");
+        assert!(decode_content(content, "rot13").is_err());
+    }
+
+    #[test]
+    fn exhausted_rate_limits_wait_for_the_reset() {
+        assert_eq!(rate_limit_delay(1, 1_000, 990), None);
+        assert_eq!(rate_limit_delay(0, 1_000, 1_001), None);
+        assert_eq!(
+            rate_limit_delay(0, 1_000, 990),
+            Some(Duration::from_secs(11))
+        );
+    }
+
+    #[test]
+    fn path_segments_are_encoded() {
+        assert_eq!(
+            encode_path_segment("good first issue/ü"),
+            "good%20first%20issue%2F%C3%BC"
+        );
+    }
+
+    #[test]
+    fn uncaptured_fields_are_collected_recursively() {
+        let text = r#"[
+            {
+                "name": "v1.0.0",
+                "zipball_url": "https://example.com/zip",
+                "tarball_url": "https://example.com/tar",
+                "commit": {"sha": "a", "url": "https://example.com/a", "verified": true},
+                "node_id": "abc",
+                "protected": false
+            },
+            {
+                "name": "v0.9.0",
+                "zipball_url": "https://example.com/zip",
+                "tarball_url": "https://example.com/tar",
+                "commit": {"sha": "b", "url": "https://example.com/b", "verified": false},
+                "node_id": "def"
+            }
+        ]"#;
+
+        let tags: Vec<TagsResponse> = parse_json(text).unwrap();
+
+        assert_eq!(
+            tags.uncaptured_fields(),
+            vec!["[].commit.verified".to_string(), "[].protected".to_string()]
+        );
+    }
+}
+
+// endregion
@@ -0,0 +1,664 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::impl_repository_scope;
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListIssues {
+    pub owner: String,
+    pub repository: String,
+    pub query: ListIssuesQuery,
+}
+
+impl Endpoint for ListIssues {
+    type Response = Vec<Issue>;
+    type Query = ListIssuesQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/issues", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &ListIssuesQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetIssue {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for GetIssue {
+    type Response = Issue;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateIssue {
+    pub owner: String,
+    pub repository: String,
+    pub issue: NewIssue,
+}
+
+impl Endpoint for CreateIssue {
+    type Response = Issue;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/issues", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.issue).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditIssue {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub update: IssueUpdate,
+}
+
+impl Endpoint for EditIssue {
+    type Response = Issue;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PATCH;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.update).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockIssue {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub lock_reason: Option<LockReason>,
+}
+
+impl Endpoint for LockIssue {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}/lock",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        self.lock_reason
+            .map(|it| serde_json::json!({ "lock_reason": it }))
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockIssue {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for UnlockIssue {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}/lock",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+pub type IssuePaginator<'a> = Paginator<'a, ListIssues>;
+
+/// Implement the issue endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the issues of a repository.
+    /// GitHub counts pull requests as issues, so they're included.
+    pub fn list_issues(
+        &self,
+        owner: &str,
+        repository: &str,
+        query: &ListIssuesQuery,
+    ) -> IssuePaginator<'_> {
+        self.paginate(ListIssues {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            query: query.clone(),
+        })
+    }
+
+    /// Gets a single issue.
+    pub fn get_issue(&self, owner: &str, repository: &str, number: u64) -> Response<Issue> {
+        self.get_endpoint(&GetIssue {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        })
+    }
+
+    pub fn create_issue(&self, owner: &str, repository: &str, issue: &NewIssue) -> Response<Issue> {
+        self.get_endpoint(&CreateIssue {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            issue: issue.clone(),
+        })
+    }
+
+    pub fn edit_issue(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        update: &IssueUpdate,
+    ) -> Response<Issue> {
+        self.get_endpoint(&EditIssue {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            update: update.clone(),
+        })
+    }
+
+    pub fn lock_issue(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        lock_reason: Option<LockReason>,
+    ) -> Response<()> {
+        self.get_endpoint(&LockIssue {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            lock_reason,
+        })
+    }
+
+    pub fn unlock_issue(&self, owner: &str, repository: &str, number: u64) -> Response<()> {
+        self.get_endpoint(&UnlockIssue {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListIssueComments {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub query: ListIssueCommentsQuery,
+}
+
+impl Endpoint for ListIssueComments {
+    type Response = Vec<IssueComment>;
+    type Query = ListIssueCommentsQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}/comments",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &ListIssueCommentsQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+/// The comments on every issue and pull request of a repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRepositoryIssueComments {
+    pub owner: String,
+    pub repository: String,
+    pub query: ListIssueCommentsQuery,
+}
+
+impl Endpoint for ListRepositoryIssueComments {
+    type Response = Vec<IssueComment>;
+    type Query = ListIssueCommentsQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/issues/comments", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &ListIssueCommentsQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateIssueComment {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub body: String,
+}
+
+impl Endpoint for CreateIssueComment {
+    type Response = IssueComment;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}/comments",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        Some(serde_json::json!({ "body": self.body }))
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditIssueComment {
+    pub owner: String,
+    pub repository: String,
+    pub comment_id: u64,
+    pub body: String,
+}
+
+impl Endpoint for EditIssueComment {
+    type Response = IssueComment;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PATCH;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/comments/{}",
+            self.owner, self.repository, self.comment_id
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        Some(serde_json::json!({ "body": self.body }))
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteIssueComment {
+    pub owner: String,
+    pub repository: String,
+    pub comment_id: u64,
+}
+
+impl Endpoint for DeleteIssueComment {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/comments/{}",
+            self.owner, self.repository, self.comment_id
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+pub type IssueCommentPaginator<'a> = Paginator<'a, ListIssueComments>;
+pub type RepositoryIssueCommentPaginator<'a> = Paginator<'a, ListRepositoryIssueComments>;
+
+/// Finds the comment written by `login` that starts with `marker`.
+fn marked_comment(comments: Vec<IssueComment>, login: &str, marker: &str) -> Option<IssueComment> {
+    comments.into_iter().find(|it| {
+        // Logins are case insensitive.
+        it.user.login.eq_ignore_ascii_case(login)
+            && it.body.as_deref().is_some_and(|it| it.starts_with(marker))
+    })
+}
+
+/// Implement the issue comment endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the comments on an issue or a pull request.
+    pub fn list_issue_comments(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        query: &ListIssueCommentsQuery,
+    ) -> IssueCommentPaginator<'_> {
+        self.paginate(ListIssueComments {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            query: query.clone(),
+        })
+    }
+
+    /// Creates a paginator over the comments on every issue and pull request of a repository.
+    pub fn list_repository_issue_comments(
+        &self,
+        owner: &str,
+        repository: &str,
+        query: &ListIssueCommentsQuery,
+    ) -> RepositoryIssueCommentPaginator<'_> {
+        self.paginate(ListRepositoryIssueComments {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            query: query.clone(),
+        })
+    }
+
+    pub fn create_issue_comment(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        body: &str,
+    ) -> Response<IssueComment> {
+        self.get_endpoint(&CreateIssueComment {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            body: body.to_string(),
+        })
+    }
+
+    pub fn edit_issue_comment(
+        &self,
+        owner: &str,
+        repository: &str,
+        comment_id: u64,
+        body: &str,
+    ) -> Response<IssueComment> {
+        self.get_endpoint(&EditIssueComment {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            comment_id,
+            body: body.to_string(),
+        })
+    }
+
+    pub fn delete_issue_comment(
+        &self,
+        owner: &str,
+        repository: &str,
+        comment_id: u64,
+    ) -> Response<()> {
+        self.get_endpoint(&DeleteIssueComment {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            comment_id,
+        })
+    }
+
+    /// Edits the comment that starts with `marker`, or creates it if there isn't one,
+    /// so that a bot can keep a single status comment up to date.
+    /// A good marker is an HTML comment, such as "<!-- build-status -->", since it isn't rendered.
+    ///
+    /// Only comments written by the authenticated user are edited,
+    /// since anyone can post a comment that starts with the marker.
+    pub fn upsert_issue_comment(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Response<IssueComment> {
+        let body = format!("{}\n{}", marker, body);
+        let login = self.get_authenticated_user()?.result.login;
+        let paginator = self.list_issue_comments(
+            owner,
+            repository,
+            number,
+            &ListIssueCommentsQuery::default(),
+        );
+        let mut next_page = Some(PageCursor::Page(1));
+
+        while let Some(cursor) = next_page {
+            let response = paginator.get_cursor(&cursor)?;
+            next_page = response.next_link.map(|it| PageCursor::Url(it.url));
+
+            if let Some(comment) = marked_comment(response.result, &login, marker) {
+                return self.edit_issue_comment(owner, repository, comment.id, &body);
+            }
+        }
+
+        self.create_issue_comment(owner, repository, number, &body)
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::UncapturedFields;
+
+    fn user(login: &str) -> String {
+        format!(
+            r#"{{
+                "login": "{0}",
+                "id": 1,
+                "node_id": "MDQ6VXNlcjE=",
+                "avatar_url": "https://github.com/images/error/{0}_happy.gif",
+                "gravatar_id": "",
+                "url": "https://api.github.com/users/{0}",
+                "html_url": "https://github.com/{0}",
+                "followers_url": "https://api.github.com/users/{0}/followers",
+                "following_url": "https://api.github.com/users/{0}/following{{/other_user}}",
+                "gists_url": "https://api.github.com/users/{0}/gists{{/gist_id}}",
+                "starred_url": "https://api.github.com/users/{0}/starred{{/owner}}{{/repo}}",
+                "subscriptions_url": "https://api.github.com/users/{0}/subscriptions",
+                "organizations_url": "https://api.github.com/users/{0}/orgs",
+                "repos_url": "https://api.github.com/users/{0}/repos",
+                "events_url": "https://api.github.com/users/{0}/events{{/privacy}}",
+                "received_events_url": "https://api.github.com/users/{0}/received_events",
+                "type": "User",
+                "site_admin": false
+            }}"#,
+            login
+        )
+    }
+
+    fn comment(id: u64, login: &str, body: &str) -> IssueComment {
+        serde_json::from_str(&format!(
+            r#"{{
+                "id": {},
+                "node_id": "MDEyOklzc3VlQ29tbWVudDE=",
+                "url": "https://api.github.com/repos/a/b/issues/comments/{0}",
+                "html_url": "https://github.com/a/b/issues/1347#issuecomment-{0}",
+                "issue_url": "https://api.github.com/repos/a/b/issues/1347",
+                "body": {},
+                "user": {},
+                "created_at": "2011-04-14T16:00:49Z",
+                "updated_at": "2011-04-14T16:00:49Z",
+                "author_association": "NONE"
+            }}"#,
+            id,
+            serde_json::to_string(body).unwrap(),
+            user(login)
+        ))
+        .unwrap()
+    }
+
+    fn issue(state_reason: &str) -> String {
+        format!(
+            r#"{{
+                "id": 1,
+                "node_id": "MDU6SXNzdWUx",
+                "url": "https://api.github.com/repos/a/b/issues/1347",
+                "repository_url": "https://api.github.com/repos/a/b",
+                "labels_url": "https://api.github.com/repos/a/b/issues/1347/labels{{/name}}",
+                "comments_url": "https://api.github.com/repos/a/b/issues/1347/comments",
+                "events_url": "https://api.github.com/repos/a/b/issues/1347/events",
+                "html_url": "https://github.com/a/b/issues/1347",
+                "number": 1347,
+                "state": "closed",
+                "state_reason": "{}",
+                "title": "Found a bug",
+                "body": null,
+                "user": {},
+                "labels": [],
+                "assignee": null,
+                "assignees": [],
+                "milestone": null,
+                "locked": false,
+                "active_lock_reason": null,
+                "comments": 0,
+                "closed_at": "2011-04-22T13:33:48Z",
+                "created_at": "2011-04-22T13:33:48Z",
+                "updated_at": "2011-04-22T13:33:48Z",
+                "closed_by": null,
+                "author_association": "OWNER"
+            }}"#,
+            state_reason,
+            user("octocat")
+        )
+    }
+
+    #[test]
+    fn only_our_own_marked_comment_is_edited() {
+        let marker = "<!-- build-status -->";
+        let comments = || {
+            vec![
+                comment(1, "octocat", "Looks good"),
+                comment(2, "mallory", "<!-- build-status -->\nfake"),
+                comment(3, "Bot", "<!-- build-status -->\npassing"),
+                comment(4, "bot", "<!-- build-status -->\nolder"),
+            ]
+        };
+
+        assert_eq!(marked_comment(comments(), "bot", marker).unwrap().id, 3);
+        assert_eq!(marked_comment(comments(), "mallory", marker).unwrap().id, 2);
+        assert!(marked_comment(comments(), "octocat", marker).is_none());
+        assert!(marked_comment(comments(), "someone", marker).is_none());
+    }
+
+    #[test]
+    fn issues_closed_for_any_reason_are_read() {
+        let duplicate: Issue = serde_json::from_str(&issue("duplicate")).unwrap();
+        let unknown: Issue = serde_json::from_str(&issue("something_new")).unwrap();
+
+        assert_eq!(duplicate.state_reason, Some(IssueStateReason::Duplicate));
+        assert_eq!(unknown.state_reason, Some(IssueStateReason::Unknown));
+        assert!(duplicate.uncaptured_fields().is_empty());
+    }
+
+    #[test]
+    fn updates_only_send_what_changes() {
+        let update = IssueUpdate {
+            state: Some(OpenClosed::Closed),
+            state_reason: Some(IssueStateReason::NotPlanned),
+            milestone: Some(None),
+            ..IssueUpdate::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&update).unwrap(),
+            r#"{"state":"closed","state_reason":"not_planned","milestone":null}"#
+        );
+
+        let lock = LockIssue {
+            owner: "a".to_string(),
+            repository: "b".to_string(),
+            number: 1,
+            lock_reason: Some(LockReason::TooHeated),
+        };
+
+        assert_eq!(lock.body().unwrap()["lock_reason"], "too heated");
+    }
+}
+
+// endregion
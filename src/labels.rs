@@ -0,0 +1,746 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::helpers::{encode_path_segment, ToJsonString};
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use crate::{impl_repository_scope, impl_to_json_string};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::error::Error as JsonError;
+use serde_json::Value;
+
+// region Label endpoints
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListLabels {
+    pub owner: String,
+    pub repository: String,
+}
+
+impl Endpoint for ListLabels {
+    type Response = Vec<Label>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/labels", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetLabel {
+    pub owner: String,
+    pub repository: String,
+    pub name: String,
+}
+
+impl Endpoint for GetLabel {
+    type Response = Label;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/labels/{}",
+            self.owner,
+            self.repository,
+            encode_path_segment(&self.name)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateLabel {
+    pub owner: String,
+    pub repository: String,
+    pub label: NewLabel,
+}
+
+impl Endpoint for CreateLabel {
+    type Response = Label;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/labels", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.label).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateLabel {
+    pub owner: String,
+    pub repository: String,
+    pub name: String,
+    pub update: LabelUpdate,
+}
+
+impl Endpoint for UpdateLabel {
+    type Response = Label;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PATCH;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/labels/{}",
+            self.owner,
+            self.repository,
+            encode_path_segment(&self.name)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.update).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteLabel {
+    pub owner: String,
+    pub repository: String,
+    pub name: String,
+}
+
+impl Endpoint for DeleteLabel {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/labels/{}",
+            self.owner,
+            self.repository,
+            encode_path_segment(&self.name)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddIssueLabels {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub labels: Vec<String>,
+}
+
+impl Endpoint for AddIssueLabels {
+    type Response = Vec<Label>;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}/labels",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        Some(serde_json::json!({ "labels": self.labels }))
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaceIssueLabels {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub labels: Vec<String>,
+}
+
+impl Endpoint for ReplaceIssueLabels {
+    type Response = Vec<Label>;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}/labels",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        Some(serde_json::json!({ "labels": self.labels }))
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveIssueLabel {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub name: String,
+}
+
+impl Endpoint for RemoveIssueLabel {
+    type Response = Vec<Label>;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/issues/{}/labels/{}",
+            self.owner,
+            self.repository,
+            self.number,
+            encode_path_segment(&self.name)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+pub type LabelPaginator<'a> = Paginator<'a, ListLabels>;
+
+// endregion
+
+// region Milestone endpoints
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListMilestones {
+    pub owner: String,
+    pub repository: String,
+    pub query: ListMilestonesQuery,
+}
+
+impl Endpoint for ListMilestones {
+    type Response = Vec<Milestone>;
+    type Query = ListMilestonesQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/milestones", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &ListMilestonesQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetMilestone {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for GetMilestone {
+    type Response = Milestone;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/milestones/{}",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMilestone {
+    pub owner: String,
+    pub repository: String,
+    pub milestone: NewMilestone,
+}
+
+impl Endpoint for CreateMilestone {
+    type Response = Milestone;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/milestones", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.milestone).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateMilestone {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub update: MilestoneUpdate,
+}
+
+impl Endpoint for UpdateMilestone {
+    type Response = Milestone;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PATCH;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/milestones/{}",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.update).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteMilestone {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for DeleteMilestone {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/milestones/{}",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+pub type MilestonePaginator<'a> = Paginator<'a, ListMilestones>;
+
+// endregion
+
+/// Implement the label endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the labels of a repository.
+    pub fn list_labels(&self, owner: &str, repository: &str) -> LabelPaginator<'_> {
+        self.paginate(ListLabels {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+        })
+    }
+
+    pub fn get_label(&self, owner: &str, repository: &str, name: &str) -> Response<Label> {
+        self.get_endpoint(&GetLabel {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    pub fn create_label(&self, owner: &str, repository: &str, label: &NewLabel) -> Response<Label> {
+        self.get_endpoint(&CreateLabel {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            label: label.clone(),
+        })
+    }
+
+    pub fn update_label(
+        &self,
+        owner: &str,
+        repository: &str,
+        name: &str,
+        update: &LabelUpdate,
+    ) -> Response<Label> {
+        self.get_endpoint(&UpdateLabel {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            name: name.to_string(),
+            update: update.clone(),
+        })
+    }
+
+    pub fn delete_label(&self, owner: &str, repository: &str, name: &str) -> Response<()> {
+        self.get_endpoint(&DeleteLabel {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    /// Adds labels to an issue or a pull request, and returns all of its labels.
+    pub fn add_issue_labels(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        labels: &[&str],
+    ) -> Response<Vec<Label>> {
+        self.get_endpoint(&AddIssueLabels {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            labels: labels.iter().map(|it| it.to_string()).collect(),
+        })
+    }
+
+    /// Replaces the labels of an issue or a pull request.
+    /// An empty list removes all of them.
+    pub fn replace_issue_labels(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        labels: &[&str],
+    ) -> Response<Vec<Label>> {
+        self.get_endpoint(&ReplaceIssueLabels {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            labels: labels.iter().map(|it| it.to_string()).collect(),
+        })
+    }
+
+    /// Removes a label from an issue or a pull request, and returns the remaining labels.
+    pub fn remove_issue_label(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        name: &str,
+    ) -> Response<Vec<Label>> {
+        self.get_endpoint(&RemoveIssueLabel {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Implement the milestone endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the milestones of a repository.
+    pub fn list_milestones(
+        &self,
+        owner: &str,
+        repository: &str,
+        query: &ListMilestonesQuery,
+    ) -> MilestonePaginator<'_> {
+        self.paginate(ListMilestones {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            query: query.clone(),
+        })
+    }
+
+    pub fn get_milestone(&self, owner: &str, repository: &str, number: u64) -> Response<Milestone> {
+        self.get_endpoint(&GetMilestone {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        })
+    }
+
+    pub fn create_milestone(
+        &self,
+        owner: &str,
+        repository: &str,
+        milestone: &NewMilestone,
+    ) -> Response<Milestone> {
+        self.get_endpoint(&CreateMilestone {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            milestone: milestone.clone(),
+        })
+    }
+
+    pub fn update_milestone(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        update: &MilestoneUpdate,
+    ) -> Response<Milestone> {
+        self.get_endpoint(&UpdateMilestone {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            update: update.clone(),
+        })
+    }
+
+    pub fn delete_milestone(&self, owner: &str, repository: &str, number: u64) -> Response<()> {
+        self.get_endpoint(&DeleteMilestone {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        })
+    }
+}
+
+// region Label sync
+
+/// A label as it should be.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelSpec {
+    pub name: String,
+
+    /// A hexadecimal color code. The leading "#" is optional.
+    pub color: String,
+    pub description: Option<String>,
+}
+
+/// A label that exists, but doesn't look like its spec.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelChange {
+    /// The name of the label as it is, which may differ in case from the spec.
+    pub current_name: String,
+    pub spec: LabelSpec,
+}
+
+/// What it takes to make the labels of a repository match the specs.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelSyncPlan {
+    pub create: Vec<LabelSpec>,
+    pub update: Vec<LabelChange>,
+
+    /// Labels that aren't in the specs. These are only deleted when asked to.
+    pub delete: Vec<String>,
+}
+impl_to_json_string!(LabelSyncPlan);
+
+impl LabelSyncPlan {
+    /// Compares the labels of a repository to the specs.
+    /// Names are compared case insensitively, since GitHub does too.
+    pub fn new(existing: &[Label], specs: &[LabelSpec]) -> Self {
+        let mut plan = Self::default();
+
+        for spec in specs {
+            let spec = LabelSpec {
+                color: normalize_color(&spec.color),
+                ..spec.clone()
+            };
+
+            match existing
+                .iter()
+                .find(|it| it.name.eq_ignore_ascii_case(&spec.name))
+            {
+                None => plan.create.push(spec),
+                Some(label) => {
+                    let unchanged = label.name == spec.name
+                        && normalize_color(&label.color) == spec.color
+                        && label.description.as_deref().unwrap_or("")
+                            == spec.description.as_deref().unwrap_or("");
+
+                    if !unchanged {
+                        plan.update.push(LabelChange {
+                            current_name: label.name.clone(),
+                            spec,
+                        });
+                    }
+                }
+            }
+        }
+
+        for label in existing {
+            if !specs
+                .iter()
+                .any(|it| it.name.eq_ignore_ascii_case(&label.name))
+            {
+                plan.delete.push(label.name.clone());
+            }
+        }
+
+        plan
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.create.is_empty() && self.update.is_empty() && self.delete.is_empty()
+    }
+}
+
+fn normalize_color(color: &str) -> String {
+    color.trim_start_matches('#').to_ascii_lowercase()
+}
+
+/// Implement the label sync.
+impl GitHubApi {
+    /// Compares the labels of a repository to the specs, and returns the differences.
+    /// When `apply` is set, labels are created and updated to match.
+    /// Labels that aren't in the specs are only deleted when `delete_unlisted` is set as well.
+    pub fn sync_labels(
+        &self,
+        owner: &str,
+        repository: &str,
+        specs: &[LabelSpec],
+        apply: bool,
+        delete_unlisted: bool,
+    ) -> Result<LabelSyncPlan, GitHubApiError> {
+        let existing = self.list_labels(owner, repository).get_all_items()?;
+        let plan = LabelSyncPlan::new(&existing, specs);
+
+        if !apply {
+            return Ok(plan);
+        }
+
+        for spec in &plan.create {
+            let label = NewLabel {
+                name: spec.name.clone(),
+                color: spec.color.clone(),
+                description: spec.description.clone(),
+            };
+            self.create_label(owner, repository, &label)?;
+        }
+
+        for change in &plan.update {
+            let update = LabelUpdate {
+                new_name: Some(change.spec.name.clone()),
+                color: Some(change.spec.color.clone()),
+                description: Some(change.spec.description.clone().unwrap_or_default()),
+            };
+            self.update_label(owner, repository, &change.current_name, &update)?;
+        }
+
+        if delete_unlisted {
+            for name in &plan.delete {
+                self.delete_label(owner, repository, name)?;
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
+// endregion
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn label(name: &str, color: &str, description: Option<&str>) -> Label {
+        Label {
+            id: 1,
+            node_id: String::new(),
+            url: String::new(),
+            name: name.to_string(),
+            color: color.to_string(),
+            default: false,
+            description: description.map(str::to_string),
+            uncaptured: HashMap::new(),
+        }
+    }
+
+    fn spec(name: &str, color: &str, description: Option<&str>) -> LabelSpec {
+        LabelSpec {
+            name: name.to_string(),
+            color: color.to_string(),
+            description: description.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn sync_plan_reconciles_labels() {
+        let existing = vec![
+            label("bug", "d73a4a", Some("Something isn't working")),
+            label("Docs", "0075ca", None),
+            label("wontfix", "ffffff", None),
+        ];
+        let specs = vec![
+            spec("bug", "#D73A4A", Some("Something isn't working")),
+            spec("docs", "0075ca", None),
+            spec("good first issue", "7057ff", None),
+        ];
+
+        let plan = LabelSyncPlan::new(&existing, &specs);
+
+        assert_eq!(plan.create, vec![spec("good first issue", "7057ff", None)]);
+        assert_eq!(
+            plan.update,
+            vec![LabelChange {
+                current_name: "Docs".to_string(),
+                spec: spec("docs", "0075ca", None),
+            }]
+        );
+        assert_eq!(plan.delete, vec!["wontfix".to_string()]);
+    }
+}
+
+// endregion
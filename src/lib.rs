@@ -1,17 +1,110 @@
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::helpers::{parse_json, HeaderMapExtensions};
-use crate::types::*;
+use crate::helpers::{parse_json, rate_limit_delay, HeaderMapExtensions};
 
-pub use helpers::ToJsonString;
+pub use crate::types::*;
+pub use archives::unpack_archive;
+pub use branches::{
+    BranchPaginator, DeleteBranchProtection, DisableRequiredSignatures, EnableRequiredSignatures,
+    GetBranch, GetBranchProtection, ListBranches, MergeBranches, RenameBranch,
+    UpdateBranchProtection,
+};
+pub use commits::{
+    CommitPaginator, CommitStatusPaginator, CompareCommits, CreateCommitStatus, GetCombinedStatus,
+    GetCommit, ListCommitStatuses, ListCommits,
+};
+pub use compliance::{
+    LicensePolicy, LicenseReport, LicenseVerdict, OwnerKind, RepositoryLicense, NO_LICENSE,
+};
+pub use contents::{CreateOrUpdateFile, DeleteFile, GetContents, GetReadme};
+pub use endpoint::{Endpoint, PaginationKind, RateLimitBucket};
+pub use git::{
+    CommitBuilder, CreateBlob, CreateGitCommit, CreateRef, CreateTag, CreateTree, DeleteRef,
+    FileChange, GetBlob, GetGitCommit, GetRef, GetTag, GetTree, ListMatchingRefs, RefPaginator,
+    UpdateRef,
+};
+pub use helpers::{ToJsonString, UncapturedFields};
+pub use issues::{
+    CreateIssue, CreateIssueComment, DeleteIssueComment, EditIssue, EditIssueComment, GetIssue,
+    IssueCommentPaginator, IssuePaginator, ListIssueComments, ListIssues,
+    ListRepositoryIssueComments, LockIssue, RepositoryIssueCommentPaginator, UnlockIssue,
+};
+pub use labels::{
+    AddIssueLabels, CreateLabel, CreateMilestone, DeleteLabel, DeleteMilestone, GetLabel,
+    GetMilestone, LabelChange, LabelPaginator, LabelSpec, LabelSyncPlan, ListLabels,
+    ListMilestones, MilestonePaginator, RemoveIssueLabel, ReplaceIssueLabels, UpdateLabel,
+    UpdateMilestone,
+};
+pub use organizations::{
+    CheckOrganizationMember, ConvertMemberToOutsideCollaborator, GetOrganization,
+    GetOrganizationMembership, ListOrganizationMembers, ListOutsideCollaborators,
+    OrganizationMemberPaginator, OutsideCollaboratorPaginator, RemoveOrganizationMember,
+    RemoveOutsideCollaborator, SetOrganizationMembership,
+};
+pub use paginator::{LenientPaginator, Paginator, PaginatorState};
+pub use pulls::{
+    CheckPullRequestMerged, CreatePullRequest, CreatePullRequestReview, CreateReviewComment,
+    DismissPullRequestReview, GetPullRequest, ListPullRequestCommits, ListPullRequestFiles,
+    ListPullRequestReviews, ListPullRequests, ListReviewComments, MergePullRequest,
+    PullRequestCommitPaginator, PullRequestFilePaginator, PullRequestPaginator,
+    PullRequestReviewPaginator, RemoveRequestedReviewers, RequestReviewers, ReviewCommentPaginator,
+    SubmitPullRequestReview, UpdatePullRequest, UpdatePullRequestBranch,
+};
+pub use repositories::{GetRepository, ListRepositories, RepositoryOwner, RepositoryPaginator};
+pub use reqwest::Method;
+pub use rulesets::{
+    BranchRulePaginator, CreateRuleset, DeleteRuleset, GetBranchRules, GetRuleset, ListRulesets,
+    RulesetPaginator, RulesetScope, UpdateRuleset,
+};
+pub use search::{
+    CodeSearchPaginator, CommitSearchPaginator, IssueSearchPaginator, LabelSearchPaginator,
+    RepositorySearchPaginator, SearchCode, SearchCommits, SearchIssues, SearchLabels,
+    SearchRepositories, SearchTopics, SearchUsers, TopicSearchPaginator, UserSearchPaginator,
+};
+pub use users::{FollowerPaginator, FollowingPaginator, GetUser, ListFollowers, ListFollowing};
 
+mod archives;
+mod branches;
+mod commits;
+mod compliance;
+mod contents;
+mod endpoint;
+mod git;
 mod helpers;
-mod macros;
+mod issues;
+mod labels;
+mod organizations;
+mod paginator;
+mod pulls;
+mod repositories;
+mod rulesets;
+mod search;
 mod types;
+mod users;
+
+const API_URL: &str = "https://api.github.com/";
+
+/// The body, the rate limits, the next link and the last link of a response.
+type RawResponse = (
+    String,
+    Option<LimitRemainingReset>,
+    Option<PageLink>,
+    Option<PageLink>,
+);
 
 pub struct GitHubApi {
     username: String,
     password: String,
+    schema_drift_mode: SchemaDriftMode,
+
+    /// What is left of the search limit, and when it resets, as of the last search.
+    search_limit: Mutex<Option<(u64, u64)>>,
 }
 
 /// Implement basic functionality.
@@ -20,42 +113,241 @@ impl GitHubApi {
         Self {
             username: username.to_string(),
             password: password.to_string(),
+            schema_drift_mode: SchemaDriftMode::Ignore,
+            search_limit: Mutex::new(None),
+        }
+    }
+
+    /// Decides what happens when GitHub returns fields that the models don't know about.
+    /// The default is to ignore them.
+    pub fn with_schema_drift_mode(mut self, mode: SchemaDriftMode) -> Self {
+        self.schema_drift_mode = mode;
+        self
+    }
+
+    /// Calls an endpoint that isn't paginated.
+    pub fn get_endpoint<E>(&self, endpoint: &E) -> Response<E::Response>
+    where
+        E: Endpoint,
+    {
+        self.call_endpoint(endpoint, &PageCursor::Page(1), 100, |text| parse_json(text))
+    }
+
+    /// Creates a paginator over any endpoint.
+    pub fn paginate<E>(&self, endpoint: E) -> Paginator<'_, E>
+    where
+        E: Endpoint,
+    {
+        Paginator::new(self, endpoint)
+    }
+
+    /// Finds the fields that the models don't know about, according to the schema drift mode.
+    fn check_schema<T>(&self, result: &T) -> Result<Vec<String>, GitHubApiError>
+    where
+        T: UncapturedFields,
+    {
+        if self.schema_drift_mode == SchemaDriftMode::Ignore {
+            return Ok(Vec::new());
+        }
+
+        let paths = result.uncaptured_fields();
+        match self.schema_drift_mode {
+            SchemaDriftMode::Error if !paths.is_empty() => Err(GitHubApiError::SchemaDrift(paths)),
+            _ => Ok(paths),
         }
     }
 
-    fn api_get_call(
+    /// Calls any endpoint, for when there's no typed function for it yet.
+    /// The path is relative to the root of the API, such as "repos/{owner}/{repo}/issues".
+    /// Empty responses, such as "204 No Content", decode as `null`, so use `()` for those.
+    /// Any type that can be deserialized works, and the schema drift check is skipped.
+    pub fn request<T, Q>(
         &self,
-        method: &str,
-        page: u64,
+        method: Method,
+        path: &str,
+        query: &Q,
+        body: Option<Value>,
+    ) -> Response<T>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        let path = path.trim_start_matches('/');
+        let mut request = self
+            .build_request(method, &format!("{}{}", API_URL, path))
+            .query(query);
+
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        self.call(request, None, None, |text| parse_json(text))
+    }
+
+    /// Like `request`, but doesn't decode the response into a model.
+    pub fn request_raw<Q>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &Q,
+        body: Option<Value>,
+    ) -> Response<Value>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.request(method, path, query, body)
+    }
+
+    /// Requests a page of an endpoint, and decodes it with `parse`.
+    fn call_endpoint<E, T>(
+        &self,
+        endpoint: &E,
+        cursor: &PageCursor,
         per_page: u64,
-    ) -> Result<(String, Option<LimitRemainingReset>, Option<u64>), GitHubApiError> {
-        let url = format!(
-            "https://api.github.com/{}?per_page={}&page={}",
-            method, per_page, page
-        );
+        parse: fn(&str) -> Result<T, GitHubApiError>,
+    ) -> Response<T>
+    where
+        E: Endpoint,
+        T: UncapturedFields,
+    {
+        let search = E::RATE_LIMIT == RateLimitBucket::Search;
+        if search {
+            self.wait_for_search_limit();
+        }
+
+        let result = self
+            .call(
+                self.endpoint_request(endpoint, cursor, per_page)?,
+                endpoint.owner(),
+                endpoint.repository(),
+                parse,
+            )
+            .and_then(|mut page| {
+                page.schema_drift = self.check_schema(&page.result)?;
+                Ok(page)
+            });
+
+        if let (true, Ok(page)) = (search, &result) {
+            if let Some(limits) = &page.limits {
+                *self.search_limit.lock().unwrap() = Some((limits.remaining, limits.reset));
+            }
+        }
+
+        result
+    }
+
+    /// Sleeps until the search limit resets, if the last search used it up.
+    fn wait_for_search_limit(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|it| it.as_secs())
+            .unwrap_or_default();
+
+        let delay = match *self.search_limit.lock().unwrap() {
+            Some((remaining, reset)) => rate_limit_delay(remaining, reset, now),
+            None => None,
+        };
+
+        if let Some(delay) = delay {
+            thread::sleep(delay);
+        }
+    }
+
+    /// Sends a request, and wraps the decoded response in the envelope.
+    fn call<T>(
+        &self,
+        request: RequestBuilder,
+        owner: Option<&str>,
+        repository: Option<&str>,
+        parse: fn(&str) -> Result<T, GitHubApiError>,
+    ) -> Response<T> {
+        let (text, limit_remaining_reset, next_link, last_link) = self.send(request)?;
+        let result = match text.trim() {
+            "" => parse("null")?,
+            text => parse(text)?,
+        };
+
+        Ok(GitHubApiResult {
+            result,
+            raw_result: text,
+            limits: limit_remaining_reset,
+            owner: owner.map(str::to_string),
+            repository: repository.map(str::to_string),
+            next_page: next_link.as_ref().and_then(PageLink::page),
+            next_link,
+            last_page: last_link.as_ref().and_then(PageLink::page),
+            schema_drift: Vec::new(),
+        })
+    }
+
+    /// Builds the request for a page of an endpoint.
+    /// Links that GitHub handed out already contain the query, so they're followed verbatim.
+    fn endpoint_request<E>(
+        &self,
+        endpoint: &E,
+        cursor: &PageCursor,
+        per_page: u64,
+    ) -> Result<RequestBuilder, GitHubApiError>
+    where
+        E: Endpoint,
+    {
+        let request = match cursor {
+            PageCursor::Page(page) => {
+                let url = match E::PAGINATION {
+                    PaginationKind::None => format!("{}{}", API_URL, endpoint.path()),
+                    _ => format!(
+                        "{}{}?per_page={}&page={}",
+                        API_URL,
+                        endpoint.path(),
+                        per_page,
+                        page
+                    ),
+                };
+
+                self.build_request(E::METHOD, &url).query(endpoint.query())
+            }
+            PageCursor::Url(url) => {
+                // Never send the credentials anywhere but to the API.
+                if !url.starts_with(API_URL) {
+                    return Err(GitHubApiError::InvalidUrl(url.clone()));
+                }
+
+                self.build_request(E::METHOD, url)
+            }
+        };
 
-        let result = Client::new()
-            .get(&url)
+        Ok(match endpoint.body() {
+            Some(body) => request.json(&body),
+            None => request,
+        })
+    }
+
+    fn build_request(&self, method: Method, url: &str) -> RequestBuilder {
+        Client::new()
+            .request(method, url)
             .header("Accept", "application/vnd.github.v3+json")
             .basic_auth(&self.username, Some(&self.password))
-            .send();
+    }
 
-        match result {
+    fn send(&self, request: RequestBuilder) -> Result<RawResponse, GitHubApiError> {
+        match request.send() {
             Ok(mut response) => {
-                let headers = response.headers();
+                let status = response.status();
 
-                if headers.is_ok() {
+                if status.is_success() {
+                    let headers = response.headers();
                     let limit_remaining_reset = headers.get_rate_limits();
-                    let next_page = headers.get_next_page();
+                    let next_link = headers.get_next_link();
+                    let last_link = headers.get_last_link();
 
                     match response.text() {
-                        Ok(text) => Ok((text, limit_remaining_reset, next_page)),
+                        Ok(text) => Ok((text, limit_remaining_reset, next_link, last_link)),
                         Err(error) => Err(GitHubApiError::ReqwestError(error)),
                     }
                 } else {
                     Err(GitHubApiError::GitHubError((
-                        headers.get("status").unwrap().to_str().unwrap().to_string(),
-                        response.text().unwrap(),
+                        status.to_string(),
+                        response.text().unwrap_or_default(),
                     )))
                 }
             }
@@ -64,46 +356,320 @@ impl GitHubApi {
     }
 }
 
+// region Endpoints
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRateLimit;
+
+impl Endpoint for GetRateLimit {
+    type Response = RateLimitResponse;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        "rate_limit".to_string()
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetLicense {
+    pub owner: String,
+    pub repository: String,
+}
+
+impl Endpoint for GetLicense {
+    type Response = LicenseResponse;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/license", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListLicenses;
+
+impl Endpoint for ListLicenses {
+    type Response = Vec<LicenseLicense>;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        "licenses".to_string()
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetLicenseByKey {
+    pub key: String,
+}
+
+impl Endpoint for GetLicenseByKey {
+    type Response = License;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!("licenses/{}", self.key)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTags {
+    pub owner: String,
+    pub repository: String,
+}
+
+impl Endpoint for ListTags {
+    type Response = Vec<TagsResponse>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/tags", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListReleases {
+    pub owner: String,
+    pub repository: String,
+}
+
+impl Endpoint for ListReleases {
+    type Response = Vec<ReleasesResponse>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/releases", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+pub type TagPaginator<'a> = Paginator<'a, ListTags>;
+pub type ReleasePaginator<'a> = Paginator<'a, ListReleases>;
+
+// endregion
+
 /// Implement rate limits.
 impl GitHubApi {
     /// Gets rate limit information.
     pub fn get_rate_limit(&self) -> Response<RateLimitResponse> {
-        let (text, limit_remaining_reset, _) = self.api_get_call("rate_limit", 1, 100)?;
+        self.get_endpoint(&GetRateLimit)
+    }
+}
 
-        Ok(GitHubApiResult {
-            result: parse_json(&text)?,
-            raw_result: text,
-            limits: limit_remaining_reset,
-            owner: None,
-            repository: None,
-            next_page: None,
+/// Implement the license endpoints.
+impl GitHubApi {
+    /// Gets the license of a repository.
+    pub fn get_license(&self, owner: &str, repository: &str) -> Response<LicenseResponse> {
+        self.get_endpoint(&GetLicense {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+        })
+    }
+
+    /// Gets the commonly used licenses.
+    pub fn get_licenses(&self) -> Response<Vec<LicenseLicense>> {
+        self.get_endpoint(&ListLicenses)
+    }
+
+    /// Gets a license by its key, such as the `key` of a `LicenseLicense`.
+    pub fn get_license_by_key(&self, key: &str) -> Response<License> {
+        self.get_endpoint(&GetLicenseByKey {
+            key: key.to_string(),
         })
     }
 }
 
-// Implement the license endpoint. No pagination.
-make_single_page_api!(get_license, "license", LicenseResponse);
+/// Implement the tags endpoint.
+impl GitHubApi {
+    /// Gets a single page.
+    pub fn get_tags_page(
+        &self,
+        owner: &str,
+        repository: &str,
+        page: u64,
+    ) -> Response<Vec<TagsResponse>> {
+        self.get_tags(owner, repository).get_page(page)
+    }
+
+    /// Creates a paginator.
+    pub fn get_tags(&self, owner: &str, repository: &str) -> TagPaginator<'_> {
+        self.paginate(ListTags {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+        })
+    }
+}
 
-// Implement the tags endpoint, including an pagination iterator.
-make_paginated_api!(get_tags, get_tags_page, "tags", TagPaginator, TagsResponse);
+/// Implement the releases endpoint.
+impl GitHubApi {
+    /// Gets a single page.
+    pub fn get_releases_page(
+        &self,
+        owner: &str,
+        repository: &str,
+        page: u64,
+    ) -> Response<Vec<ReleasesResponse>> {
+        self.get_releases(owner, repository).get_page(page)
+    }
 
-// Implement the releases endpoint, including an pagination iterator.
-make_paginated_api!(
-    get_releases,
-    get_releases_page,
-    "releases",
-    ReleasePaginator,
-    ReleasesResponse
-);
+    /// Creates a paginator.
+    pub fn get_releases(&self, owner: &str, repository: &str) -> ReleasePaginator<'_> {
+        self.paginate(ListReleases {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+        })
+    }
+}
 
 // region Tests
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+    struct TestQuery {
+        state: Option<OpenClosed>,
+        labels: String,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct ListReleaseAssets {
+        owner: String,
+        repository: String,
+        release_id: u64,
+        query: TestQuery,
+    }
+
+    impl Endpoint for ListReleaseAssets {
+        type Response = Vec<ReleasesAsset>;
+        type Query = TestQuery;
+
+        const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+        fn path(&self) -> String {
+            format!(
+                "repos/{}/{}/releases/{}/assets",
+                self.owner, self.repository, self.release_id
+            )
+        }
+
+        fn query(&self) -> &TestQuery {
+            &self.query
+        }
+    }
+
+    #[test]
+    fn schema_drift_is_returned_instead_of_printed() {
+        let commit: TagsCommit =
+            parse_json(r#"{"sha": "abc", "url": "https://example.com", "verified": true}"#)
+                .unwrap();
+
+        let warn = GitHubApi::new("", "").with_schema_drift_mode(SchemaDriftMode::Warn);
+        let error = GitHubApi::new("", "").with_schema_drift_mode(SchemaDriftMode::Error);
+
+        assert_eq!(warn.check_schema(&commit).unwrap(), vec!["verified"]);
+        assert!(error.check_schema(&commit).is_err());
+        assert!(GitHubApi::new("", "")
+            .check_schema(&commit)
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    #[test]
+    fn paginators_resume_from_their_state() {
+        let gh = GitHubApi::new("username", "password");
+        let paginator = gh.get_tags("a", "b").starting_at(5);
+
+        let json = paginator.state().to_json_string().unwrap();
+        let state: PaginatorState<ListTags> = serde_json::from_str(&json).unwrap();
+        let resumed = TagPaginator::from_state(&gh, state);
+
+        assert_eq!(resumed.state().next_page, Some(PageCursor::Page(5)));
+        assert_eq!(resumed.endpoint().path(), "repos/a/b/tags");
+        assert!(serde_json::from_str::<PaginatorState<GetLicenseByKey>>(&json).is_err());
+    }
+
+    #[test]
+    fn query_is_url_encoded_after_the_page() {
+        let gh = GitHubApi::new("username", "password");
+        let endpoint = ListReleaseAssets {
+            owner: "a".to_string(),
+            repository: "b".to_string(),
+            release_id: 1,
+            query: TestQuery {
+                state: Some(OpenClosed::Closed),
+                labels: "bug,good first issue".to_string(),
+            },
+        };
+
+        let request = gh
+            .endpoint_request(&endpoint, &PageCursor::Page(2), 100)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.github.com/repos/a/b/releases/1/assets?per_page=100&page=2&state=closed&labels=bug%2Cgood+first+issue"
+        );
+    }
+
+    #[test]
+    fn single_responses_are_not_paged() {
+        let gh = GitHubApi::new("username", "password");
+        let request = gh
+            .endpoint_request(&GetRateLimit, &PageCursor::Page(1), 100)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(request.url().as_str(), "https://api.github.com/rate_limit");
+        assert!(gh
+            .endpoint_request(
+                &GetRateLimit,
+                &PageCursor::Url("https://example.com/rate_limit".to_string()),
+                100
+            )
+            .is_err());
+    }
 }
 
 // endregion
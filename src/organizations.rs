@@ -0,0 +1,388 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetOrganization {
+    pub organization: String,
+}
+
+impl Endpoint for GetOrganization {
+    type Response = Organization;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!("orgs/{}", self.organization)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListOrganizationMembers {
+    pub organization: String,
+    pub query: ListMembersQuery,
+}
+
+impl Endpoint for ListOrganizationMembers {
+    type Response = Vec<GenericPerson>;
+    type Query = ListMembersQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("orgs/{}/members", self.organization)
+    }
+
+    fn query(&self) -> &ListMembersQuery {
+        &self.query
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+/// Answers with 204 for members, and 404 for everyone else.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckOrganizationMember {
+    pub organization: String,
+    pub username: String,
+}
+
+impl Endpoint for CheckOrganizationMember {
+    type Response = ();
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!("orgs/{}/members/{}", self.organization, self.username)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+/// Removes a user from the organization, along with their access to its repositories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveOrganizationMember {
+    pub organization: String,
+    pub username: String,
+}
+
+impl Endpoint for RemoveOrganizationMember {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!("orgs/{}/members/{}", self.organization, self.username)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetOrganizationMembership {
+    pub organization: String,
+    pub username: String,
+}
+
+impl Endpoint for GetOrganizationMembership {
+    type Response = OrganizationMembership;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!("orgs/{}/memberships/{}", self.organization, self.username)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+/// Invites a user to the organization, or changes the role of a member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetOrganizationMembership {
+    pub organization: String,
+    pub username: String,
+    pub update: MembershipUpdate,
+}
+
+impl Endpoint for SetOrganizationMembership {
+    type Response = OrganizationMembership;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!("orgs/{}/memberships/{}", self.organization, self.username)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.update).ok()
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+/// Lists the users that have access to repositories of the organization without being members.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListOutsideCollaborators {
+    pub organization: String,
+    pub query: ListOutsideCollaboratorsQuery,
+}
+
+impl Endpoint for ListOutsideCollaborators {
+    type Response = Vec<GenericPerson>;
+    type Query = ListOutsideCollaboratorsQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("orgs/{}/outside_collaborators", self.organization)
+    }
+
+    fn query(&self) -> &ListOutsideCollaboratorsQuery {
+        &self.query
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+/// Turns a member into an outside collaborator, who keeps access to the repositories they had it to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertMemberToOutsideCollaborator {
+    pub organization: String,
+    pub username: String,
+}
+
+impl Endpoint for ConvertMemberToOutsideCollaborator {
+    type Response = Value;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!(
+            "orgs/{}/outside_collaborators/{}",
+            self.organization, self.username
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveOutsideCollaborator {
+    pub organization: String,
+    pub username: String,
+}
+
+impl Endpoint for RemoveOutsideCollaborator {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "orgs/{}/outside_collaborators/{}",
+            self.organization, self.username
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        Some(&self.organization)
+    }
+}
+
+pub type OrganizationMemberPaginator<'a> = Paginator<'a, ListOrganizationMembers>;
+pub type OutsideCollaboratorPaginator<'a> = Paginator<'a, ListOutsideCollaborators>;
+
+/// Implement the organization endpoints.
+impl GitHubApi {
+    pub fn get_org(&self, organization: &str) -> Response<Organization> {
+        self.get_endpoint(&GetOrganization {
+            organization: organization.to_string(),
+        })
+    }
+
+    /// Creates a paginator over the members of an organization, optionally only those with `role`.
+    pub fn list_org_members(
+        &self,
+        organization: &str,
+        role: Option<MemberRoleFilter>,
+    ) -> OrganizationMemberPaginator<'_> {
+        self.paginate(ListOrganizationMembers {
+            organization: organization.to_string(),
+            query: ListMembersQuery { filter: None, role },
+        })
+    }
+
+    /// Tells whether a user is a member of an organization.
+    /// Unless the authenticated user is a member too, only public members are found.
+    pub fn is_org_member(
+        &self,
+        organization: &str,
+        username: &str,
+    ) -> Result<bool, GitHubApiError> {
+        let endpoint = CheckOrganizationMember {
+            organization: organization.to_string(),
+            username: username.to_string(),
+        };
+
+        match self.get_endpoint(&endpoint) {
+            Ok(_) => Ok(true),
+            Err(ref error) if error.is_not_found() => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn remove_org_member(&self, organization: &str, username: &str) -> Response<()> {
+        self.get_endpoint(&RemoveOrganizationMember {
+            organization: organization.to_string(),
+            username: username.to_string(),
+        })
+    }
+
+    /// Gets the role of a user in an organization, and whether they have accepted the invitation.
+    pub fn get_org_membership(
+        &self,
+        organization: &str,
+        username: &str,
+    ) -> Response<OrganizationMembership> {
+        self.get_endpoint(&GetOrganizationMembership {
+            organization: organization.to_string(),
+            username: username.to_string(),
+        })
+    }
+
+    /// Invites a user with `role`, or changes the role of a member.
+    /// Only `Admin` and `Member` can be given this way.
+    pub fn set_org_membership(
+        &self,
+        organization: &str,
+        username: &str,
+        role: OrganizationRole,
+    ) -> Response<OrganizationMembership> {
+        self.get_endpoint(&SetOrganizationMembership {
+            organization: organization.to_string(),
+            username: username.to_string(),
+            update: MembershipUpdate { role },
+        })
+    }
+
+    /// Creates a paginator over the outside collaborators of an organization.
+    pub fn list_outside_collaborators(
+        &self,
+        organization: &str,
+        query: &ListOutsideCollaboratorsQuery,
+    ) -> OutsideCollaboratorPaginator<'_> {
+        self.paginate(ListOutsideCollaborators {
+            organization: organization.to_string(),
+            query: query.clone(),
+        })
+    }
+
+    pub fn convert_member_to_outside_collaborator(
+        &self,
+        organization: &str,
+        username: &str,
+    ) -> Response<Value> {
+        self.get_endpoint(&ConvertMemberToOutsideCollaborator {
+            organization: organization.to_string(),
+            username: username.to_string(),
+        })
+    }
+
+    pub fn remove_outside_collaborator(&self, organization: &str, username: &str) -> Response<()> {
+        self.get_endpoint(&RemoveOutsideCollaborator {
+            organization: organization.to_string(),
+            username: username.to_string(),
+        })
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::UncapturedFields;
+
+    #[test]
+    fn memberships_are_decoded() {
+        let text = r#"{
+            "url": "https://api.github.com/orgs/octo-org/memberships/defunkt",
+            "state": "pending",
+            "role": "billing_manager",
+            "organization_url": "https://api.github.com/orgs/octo-org",
+            "organization": {
+                "login": "octo-org",
+                "id": 1,
+                "node_id": "MDEyOk9yZ2FuaXphdGlvbjE=",
+                "url": "https://api.github.com/orgs/octo-org",
+                "repos_url": "https://api.github.com/orgs/octo-org/repos",
+                "events_url": "https://api.github.com/orgs/octo-org/events",
+                "hooks_url": "https://api.github.com/orgs/octo-org/hooks",
+                "issues_url": "https://api.github.com/orgs/octo-org/issues",
+                "members_url": "https://api.github.com/orgs/octo-org/members{/member}",
+                "public_members_url": "https://api.github.com/orgs/octo-org/public_members{/member}",
+                "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+                "description": null
+            }
+        }"#;
+
+        let membership: OrganizationMembership = serde_json::from_str(text).unwrap();
+
+        assert_eq!(membership.state, MembershipState::Pending);
+        assert_eq!(membership.role, OrganizationRole::BillingManager);
+        assert!(membership.uncaptured_fields().is_empty());
+    }
+}
+
+// endregion
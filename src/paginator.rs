@@ -0,0 +1,381 @@
+use crate::endpoint::Endpoint;
+use crate::helpers::{parse_json, parse_json_lenient, ToJsonString, UncapturedFields};
+use crate::types::*;
+use crate::GitHubApi;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::error::Error as JsonError;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Everything a paginator needs to continue where it left off.
+/// Save it after each page, and resume later with `from_state`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatorState<E> {
+    pub endpoint: E,
+
+    /// This is `None` when there are no more pages.
+    pub next_page: Option<PageCursor>,
+}
+
+impl<E> ToJsonString for PaginatorState<E>
+where
+    E: Serialize,
+{
+    fn to_json_string(&self) -> Result<String, JsonError> {
+        serde_json::to_string(self)
+    }
+}
+
+/// Iterates over the pages of any endpoint.
+/// Create one with `GitHubApi::paginate`.
+pub struct Paginator<'a, E> {
+    github_api: &'a GitHubApi,
+    endpoint: E,
+    next_page: Option<PageCursor>,
+}
+
+impl<'a, E> Paginator<'a, E>
+where
+    E: Endpoint,
+{
+    pub fn new(github_api: &'a GitHubApi, endpoint: E) -> Self {
+        Self {
+            github_api,
+            endpoint,
+            next_page: Some(PageCursor::Page(1)),
+        }
+    }
+
+    pub fn endpoint(&self) -> &E {
+        &self.endpoint
+    }
+
+    /// Makes the paginator continue from an arbitrary page.
+    pub fn starting_at(mut self, page: u64) -> Self {
+        self.next_page = Some(PageCursor::Page(page));
+        self
+    }
+
+    /// Gets a snapshot of where the paginator is, which can be serialized.
+    pub fn state(&self) -> PaginatorState<E>
+    where
+        E: Clone,
+    {
+        PaginatorState {
+            endpoint: self.endpoint.clone(),
+            next_page: self.next_page.clone(),
+        }
+    }
+
+    /// Resumes a paginator from a snapshot taken with `state`.
+    pub fn from_state(github_api: &'a GitHubApi, state: PaginatorState<E>) -> Self {
+        Self {
+            github_api,
+            endpoint: state.endpoint,
+            next_page: state.next_page,
+        }
+    }
+
+    /// Gets a single page.
+    pub fn get_page(&self, page: u64) -> Response<E::Response> {
+        self.get_cursor(&PageCursor::Page(page))
+    }
+
+    /// Gets a page by number, or by following a link.
+    pub fn get_cursor(&self, cursor: &PageCursor) -> Response<E::Response> {
+        self.github_api
+            .call_endpoint(&self.endpoint, cursor, 100, |text| parse_json(text))
+    }
+
+    /// Gets every remaining page, using up to `workers` threads at a time.
+    /// The pages are returned in order.
+    pub fn get_all_concurrently(
+        &self,
+        workers: usize,
+    ) -> Result<Vec<GitHubApiResult<E::Response>>, GitHubApiError>
+    where
+        E: Sync,
+        E::Response: Send,
+    {
+        let get_cursor = |cursor: &PageCursor| self.get_cursor(cursor);
+
+        match &self.next_page {
+            Some(cursor) => get_pages_concurrently(get_cursor(cursor)?, workers, get_cursor),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl<'a, E, T> Paginator<'a, E>
+where
+    E: Endpoint<Response = Vec<T>>,
+    T: DeserializeOwned + UncapturedFields,
+{
+    pub fn has_items(&self) -> Result<bool, GitHubApiError> {
+        let page: GitHubApiResult<Vec<T>> =
+            self.github_api
+                .call_endpoint(&self.endpoint, &PageCursor::Page(1), 1, |text| {
+                    parse_json(text)
+                })?;
+
+        Ok(!page.result.is_empty())
+    }
+
+    /// Gets the items of every remaining page, one page at a time.
+    /// Unlike the iterator, this stops at the first error and returns it.
+    pub fn get_all_items(&self) -> Result<Vec<T>, GitHubApiError> {
+        let mut items = Vec::new();
+        let mut next_page = self.next_page.clone();
+
+        while let Some(cursor) = next_page {
+            let page = self.get_cursor(&cursor)?;
+            next_page = page.next_link.map(|it| PageCursor::Url(it.url));
+            items.extend(page.result);
+        }
+
+        Ok(items)
+    }
+
+    /// Turns this into a paginator that decodes every item on its own,
+    /// so that a single malformed item doesn't reject the whole page.
+    pub fn lenient(self) -> LenientPaginator<'a, E> {
+        LenientPaginator { paginator: self }
+    }
+}
+
+impl<'a, E> Iterator for Paginator<'a, E>
+where
+    E: Endpoint,
+{
+    type Item = GitHubApiResult<E::Response>;
+
+    /// Gets the next page.
+    /// Stops on an error, and tries the same page again when called again.
+    /// Use `get_cursor` to see the error.
+    fn next(&mut self) -> Option<Self::Item> {
+        let cursor = self.next_page.take()?;
+
+        match self.get_cursor(&cursor) {
+            Ok(page) => {
+                self.next_page = page
+                    .next_link
+                    .as_ref()
+                    .map(|it| PageCursor::Url(it.url.clone()));
+                Some(page)
+            }
+            Err(_) => {
+                self.next_page = Some(cursor);
+                None
+            }
+        }
+    }
+}
+
+/// A paginator that decodes every item of a page separately.
+/// Create one by calling `lenient()` on a paginator.
+pub struct LenientPaginator<'a, E> {
+    paginator: Paginator<'a, E>,
+}
+
+impl<'a, E, T> LenientPaginator<'a, E>
+where
+    E: Endpoint<Response = Vec<T>>,
+    T: DeserializeOwned + UncapturedFields,
+{
+    /// Makes the paginator continue from an arbitrary page.
+    pub fn starting_at(self, page: u64) -> Self {
+        Self {
+            paginator: self.paginator.starting_at(page),
+        }
+    }
+
+    /// Gets a snapshot of where the paginator is, which can be serialized.
+    pub fn state(&self) -> PaginatorState<E>
+    where
+        E: Clone,
+    {
+        self.paginator.state()
+    }
+
+    /// Resumes a paginator from a snapshot taken with `state`.
+    pub fn from_state(github_api: &'a GitHubApi, state: PaginatorState<E>) -> Self {
+        Self {
+            paginator: Paginator::from_state(github_api, state),
+        }
+    }
+
+    /// Gets a single page.
+    pub fn get_page(&self, page: u64) -> Response<LenientPage<T>> {
+        self.get_cursor(&PageCursor::Page(page))
+    }
+
+    /// Gets a page by number, or by following a link.
+    pub fn get_cursor(&self, cursor: &PageCursor) -> Response<LenientPage<T>> {
+        self.paginator
+            .github_api
+            .call_endpoint(&self.paginator.endpoint, cursor, 100, |text| {
+                parse_json_lenient(text)
+            })
+    }
+}
+
+impl<'a, E, T> Iterator for LenientPaginator<'a, E>
+where
+    E: Endpoint<Response = Vec<T>>,
+    T: DeserializeOwned + UncapturedFields,
+{
+    type Item = GitHubApiResult<LenientPage<T>>;
+
+    /// Gets the next page.
+    /// Stops on an error, and tries the same page again when called again.
+    /// Use `get_cursor` to see the error.
+    fn next(&mut self) -> Option<Self::Item> {
+        let cursor = self.paginator.next_page.take()?;
+
+        match self.get_cursor(&cursor) {
+            Ok(page) => {
+                self.paginator.next_page = page
+                    .next_link
+                    .as_ref()
+                    .map(|it| PageCursor::Url(it.url.clone()));
+                Some(page)
+            }
+            Err(_) => {
+                self.paginator.next_page = Some(cursor);
+                None
+            }
+        }
+    }
+}
+
+/// Gets the pages following `first`, using `workers` threads,
+/// and returns every page including the first one, in page order.
+///
+/// The last page number is taken from the "last" link of the first page.
+/// Endpoints that don't hand out one, such as the ones using cursors, are paged sequentially.
+pub fn get_pages_concurrently<T, F>(
+    first: GitHubApiResult<T>,
+    workers: usize,
+    get_cursor: F,
+) -> Result<Vec<GitHubApiResult<T>>, GitHubApiError>
+where
+    T: Send,
+    F: Fn(&PageCursor) -> Response<T> + Sync,
+{
+    let first_page = first.next_page.map(|it| it.saturating_sub(1));
+    let (first_page, last_page) = match (first_page, first.last_page) {
+        (Some(first_page), Some(last_page)) => (first_page, last_page),
+        _ => return get_pages_sequentially(first, get_cursor),
+    };
+
+    let next_page = AtomicU64::new(first_page + 1);
+    let pages = Mutex::new(BTreeMap::new());
+
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            scope.spawn(|| loop {
+                let page = next_page.fetch_add(1, Ordering::SeqCst);
+                if page > last_page {
+                    break;
+                }
+
+                let result = get_cursor(&PageCursor::Page(page));
+                let failed = result.is_err();
+                pages.lock().unwrap().insert(page, result);
+
+                if failed {
+                    // Make the other workers stop as well.
+                    next_page.store(last_page + 1, Ordering::SeqCst);
+                    break;
+                }
+            });
+        }
+    });
+
+    let mut results = vec![first];
+    for (_, page) in pages.into_inner().unwrap() {
+        results.push(page?);
+    }
+
+    Ok(results)
+}
+
+fn get_pages_sequentially<T, F>(
+    first: GitHubApiResult<T>,
+    get_cursor: F,
+) -> Result<Vec<GitHubApiResult<T>>, GitHubApiError>
+where
+    F: Fn(&PageCursor) -> Response<T>,
+{
+    let mut next_page = first
+        .next_link
+        .as_ref()
+        .map(|it| PageCursor::Url(it.url.clone()));
+    let mut results = vec![first];
+
+    while let Some(cursor) = next_page {
+        let page = get_cursor(&cursor)?;
+        next_page = page
+            .next_link
+            .as_ref()
+            .map(|it| PageCursor::Url(it.url.clone()));
+        results.push(page);
+    }
+
+    Ok(results)
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(number: u64, last_page: u64) -> GitHubApiResult<u64> {
+        let next_link = if number < last_page {
+            PageLink::parse(&format!("https://api.github.com/a?page={}", number + 1))
+        } else {
+            None
+        };
+
+        GitHubApiResult {
+            result: number,
+            raw_result: String::new(),
+            limits: None,
+            owner: None,
+            repository: None,
+            next_page: next_link.as_ref().and_then(PageLink::page),
+            next_link,
+            last_page: Some(last_page),
+            schema_drift: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn concurrent_pages_are_returned_in_order() {
+        let pages = get_pages_concurrently(page(1, 25), 4, |cursor| match cursor {
+            PageCursor::Page(number) => Ok(page(*number, 25)),
+            PageCursor::Url(url) => panic!("Unexpected url: {}", url),
+        })
+        .unwrap();
+
+        let numbers: Vec<u64> = pages.into_iter().map(|it| it.result).collect();
+        assert_eq!(numbers, (1..=25).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn concurrent_paging_stops_at_the_first_error() {
+        let result = get_pages_concurrently(page(1, 25), 4, |cursor| match cursor {
+            PageCursor::Page(7) => Err(GitHubApiError::NotImplemented),
+            PageCursor::Page(number) => Ok(page(*number, 25)),
+            PageCursor::Url(url) => panic!("Unexpected url: {}", url),
+        });
+
+        assert!(result.is_err());
+    }
+}
+
+// endregion
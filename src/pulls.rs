@@ -0,0 +1,876 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::impl_repository_scope;
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListPullRequests {
+    pub owner: String,
+    pub repository: String,
+    pub query: ListPullRequestsQuery,
+}
+
+impl Endpoint for ListPullRequests {
+    type Response = Vec<PullRequest>;
+    type Query = ListPullRequestsQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/pulls", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &ListPullRequestsQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPullRequest {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for GetPullRequest {
+    type Response = PullRequest;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePullRequest {
+    pub owner: String,
+    pub repository: String,
+    pub pull_request: NewPullRequest,
+}
+
+impl Endpoint for CreatePullRequest {
+    type Response = PullRequest;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}/pulls", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.pull_request).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdatePullRequest {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub update: PullRequestUpdate,
+}
+
+impl Endpoint for UpdatePullRequest {
+    type Response = PullRequest;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PATCH;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.update).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListPullRequestCommits {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for ListPullRequestCommits {
+    type Response = Vec<Commit>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/commits",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListPullRequestFiles {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for ListPullRequestFiles {
+    type Response = Vec<CommitFile>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/files",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+/// Answers "204 No Content" if the pull request was merged, and "404 Not Found" if it wasn't.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckPullRequestMerged {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for CheckPullRequestMerged {
+    type Response = ();
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/merge",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergePullRequest {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub merge: PullRequestMerge,
+}
+
+impl Endpoint for MergePullRequest {
+    type Response = MergeResult;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/merge",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.merge).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+/// Merges the base branch into the head branch of a pull request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdatePullRequestBranch {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+
+    /// The update fails if the head of the pull request isn't this commit anymore.
+    pub expected_head_sha: Option<String>,
+}
+
+impl Endpoint for UpdatePullRequestBranch {
+    type Response = UpdateBranchResult;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/update-branch",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        Some(match &self.expected_head_sha {
+            Some(sha) => serde_json::json!({ "expected_head_sha": sha }),
+            None => serde_json::json!({}),
+        })
+    }
+
+    impl_repository_scope!();
+}
+
+pub type PullRequestPaginator<'a> = Paginator<'a, ListPullRequests>;
+pub type PullRequestCommitPaginator<'a> = Paginator<'a, ListPullRequestCommits>;
+pub type PullRequestFilePaginator<'a> = Paginator<'a, ListPullRequestFiles>;
+
+/// Implement the pull request endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the pull requests of a repository.
+    pub fn list_pull_requests(
+        &self,
+        owner: &str,
+        repository: &str,
+        query: &ListPullRequestsQuery,
+    ) -> PullRequestPaginator<'_> {
+        self.paginate(ListPullRequests {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            query: query.clone(),
+        })
+    }
+
+    /// Gets a single pull request, including whether it's mergeable.
+    pub fn get_pull_request(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+    ) -> Response<PullRequest> {
+        self.get_endpoint(&GetPullRequest {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        })
+    }
+
+    pub fn create_pull_request(
+        &self,
+        owner: &str,
+        repository: &str,
+        pull_request: &NewPullRequest,
+    ) -> Response<PullRequest> {
+        self.get_endpoint(&CreatePullRequest {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            pull_request: pull_request.clone(),
+        })
+    }
+
+    pub fn update_pull_request(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        update: &PullRequestUpdate,
+    ) -> Response<PullRequest> {
+        self.get_endpoint(&UpdatePullRequest {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            update: update.clone(),
+        })
+    }
+
+    /// Creates a paginator over the commits of a pull request.
+    /// GitHub only returns the first 250.
+    pub fn list_pull_request_commits(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+    ) -> PullRequestCommitPaginator<'_> {
+        self.paginate(ListPullRequestCommits {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        })
+    }
+
+    /// Creates a paginator over the changed files of a pull request.
+    /// GitHub only returns the first 3000.
+    pub fn list_pull_request_files(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+    ) -> PullRequestFilePaginator<'_> {
+        self.paginate(ListPullRequestFiles {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        })
+    }
+
+    /// Tells whether a pull request has been merged.
+    pub fn is_pull_request_merged(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+    ) -> Result<bool, GitHubApiError> {
+        let endpoint = CheckPullRequestMerged {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        };
+
+        match self.get_endpoint(&endpoint) {
+            Ok(_) => Ok(true),
+            Err(ref error) if error.is_not_found() => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn merge_pull_request(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        merge: &PullRequestMerge,
+    ) -> Response<MergeResult> {
+        self.get_endpoint(&MergePullRequest {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            merge: merge.clone(),
+        })
+    }
+
+    /// Merges the base branch into the head branch of a pull request.
+    /// GitHub does this in the background, so the branch isn't updated right away.
+    pub fn update_pull_request_branch(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        expected_head_sha: Option<&str>,
+    ) -> Response<UpdateBranchResult> {
+        self.get_endpoint(&UpdatePullRequestBranch {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            expected_head_sha: expected_head_sha.map(str::to_string),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListPullRequestReviews {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+}
+
+impl Endpoint for ListPullRequestReviews {
+    type Response = Vec<PullRequestReview>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/reviews",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePullRequestReview {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub review: NewReview,
+}
+
+impl Endpoint for CreatePullRequestReview {
+    type Response = PullRequestReview;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/reviews",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.review).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+/// Submits a pending review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitPullRequestReview {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub review_id: u64,
+    pub event: ReviewEvent,
+    pub body: Option<String>,
+}
+
+impl Endpoint for SubmitPullRequestReview {
+    type Response = PullRequestReview;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/reviews/{}/events",
+            self.owner, self.repository, self.number, self.review_id
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        let mut body = serde_json::json!({ "event": self.event });
+        if let Some(text) = &self.body {
+            body["body"] = Value::String(text.clone());
+        }
+        Some(body)
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DismissPullRequestReview {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub review_id: u64,
+    pub message: String,
+}
+
+impl Endpoint for DismissPullRequestReview {
+    type Response = PullRequestReview;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/reviews/{}/dismissals",
+            self.owner, self.repository, self.number, self.review_id
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        Some(serde_json::json!({ "message": self.message, "event": "DISMISS" }))
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestReviewers {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub requests: ReviewRequests,
+}
+
+impl Endpoint for RequestReviewers {
+    type Response = PullRequest;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/requested_reviewers",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.requests).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveRequestedReviewers {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub requests: ReviewRequests,
+}
+
+impl Endpoint for RemoveRequestedReviewers {
+    type Response = PullRequest;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/requested_reviewers",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        // GitHub requires the list of users, even when it's empty.
+        Some(serde_json::json!({
+            "reviewers": self.requests.reviewers,
+            "team_reviewers": self.requests.team_reviewers,
+        }))
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListReviewComments {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub query: ListReviewCommentsQuery,
+}
+
+impl Endpoint for ListReviewComments {
+    type Response = Vec<ReviewComment>;
+    type Query = ListReviewCommentsQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/comments",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &ListReviewCommentsQuery {
+        &self.query
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateReviewComment {
+    pub owner: String,
+    pub repository: String,
+    pub number: u64,
+    pub comment: NewReviewComment,
+}
+
+impl Endpoint for CreateReviewComment {
+    type Response = ReviewComment;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/pulls/{}/comments",
+            self.owner, self.repository, self.number
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.comment).ok()
+    }
+
+    impl_repository_scope!();
+}
+
+pub type PullRequestReviewPaginator<'a> = Paginator<'a, ListPullRequestReviews>;
+pub type ReviewCommentPaginator<'a> = Paginator<'a, ListReviewComments>;
+
+/// Implement the pull request review endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the reviews of a pull request, oldest first.
+    pub fn list_pull_request_reviews(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+    ) -> PullRequestReviewPaginator<'_> {
+        self.paginate(ListPullRequestReviews {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+        })
+    }
+
+    pub fn create_pull_request_review(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        review: &NewReview,
+    ) -> Response<PullRequestReview> {
+        self.get_endpoint(&CreatePullRequestReview {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            review: review.clone(),
+        })
+    }
+
+    /// Submits a review that was created without an event.
+    pub fn submit_pull_request_review(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        review_id: u64,
+        event: ReviewEvent,
+        body: Option<&str>,
+    ) -> Response<PullRequestReview> {
+        self.get_endpoint(&SubmitPullRequestReview {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            review_id,
+            event,
+            body: body.map(str::to_string),
+        })
+    }
+
+    pub fn dismiss_pull_request_review(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        review_id: u64,
+        message: &str,
+    ) -> Response<PullRequestReview> {
+        self.get_endpoint(&DismissPullRequestReview {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            review_id,
+            message: message.to_string(),
+        })
+    }
+
+    pub fn request_reviewers(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        requests: &ReviewRequests,
+    ) -> Response<PullRequest> {
+        self.get_endpoint(&RequestReviewers {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            requests: requests.clone(),
+        })
+    }
+
+    pub fn remove_requested_reviewers(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        requests: &ReviewRequests,
+    ) -> Response<PullRequest> {
+        self.get_endpoint(&RemoveRequestedReviewers {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            requests: requests.clone(),
+        })
+    }
+
+    /// Creates a paginator over the comments on the diff of a pull request.
+    pub fn list_review_comments(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        query: &ListReviewCommentsQuery,
+    ) -> ReviewCommentPaginator<'_> {
+        self.paginate(ListReviewComments {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            query: query.clone(),
+        })
+    }
+
+    /// Comments on the diff of a pull request, or replies to such a comment.
+    pub fn create_review_comment(
+        &self,
+        owner: &str,
+        repository: &str,
+        number: u64,
+        comment: &NewReviewComment,
+    ) -> Response<ReviewComment> {
+        self.get_endpoint(&CreateReviewComment {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            number,
+            comment: comment.clone(),
+        })
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn submitted_reviews_leave_out_a_missing_body() {
+        let review = |body: Option<&str>| SubmitPullRequestReview {
+            owner: "a".to_string(),
+            repository: "b".to_string(),
+            number: 1,
+            review_id: 2,
+            event: ReviewEvent::Approve,
+            body: body.map(str::to_string),
+        };
+
+        assert_eq!(
+            review(None).body().unwrap(),
+            serde_json::json!({"event": "APPROVE"})
+        );
+        assert_eq!(
+            review(Some("LGTM")).body().unwrap(),
+            serde_json::json!({"event": "APPROVE", "body": "LGTM"})
+        );
+    }
+
+    #[test]
+    fn merges_can_expect_a_head_sha() {
+        let merge = PullRequestMerge {
+            sha: Some("abc".to_string()),
+            merge_method: Some(MergeMethod::Squash),
+            ..PullRequestMerge::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&merge).unwrap(),
+            r#"{"sha":"abc","merge_method":"squash"}"#
+        );
+
+        let query = ListPullRequestsQuery {
+            sort: Some(PullRequestSort::LongRunning),
+            ..ListPullRequestsQuery::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&query).unwrap(),
+            r#"{"sort":"long-running"}"#
+        );
+    }
+
+    #[test]
+    fn review_comments_can_span_lines() {
+        let comment = NewReviewComment {
+            body: "Extract this.".to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(12),
+            side: Some(DiffSide::Right),
+            start_line: Some(10),
+            ..NewReviewComment::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&comment).unwrap(),
+            r#"{"body":"Extract this.","path":"src/lib.rs","line":12,"side":"RIGHT","start_line":10}"#
+        );
+    }
+}
+
+// endregion
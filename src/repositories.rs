@@ -0,0 +1,149 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::impl_repository_scope;
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use serde::{Deserialize, Serialize};
+
+/// Whose repositories to list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RepositoryOwner {
+    User(String),
+    Organization(String),
+
+    /// The repositories that the authenticated user can access.
+    AuthenticatedUser,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRepository {
+    pub owner: String,
+    pub repository: String,
+}
+
+impl Endpoint for GetRepository {
+    type Response = Repository;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!("repos/{}/{}", self.owner, self.repository)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRepositories {
+    pub owner: RepositoryOwner,
+    pub query: ListRepositoriesQuery,
+}
+
+impl Endpoint for ListRepositories {
+    type Response = Vec<Repository>;
+    type Query = ListRepositoriesQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        match &self.owner {
+            RepositoryOwner::User(username) => format!("users/{}/repos", username),
+            RepositoryOwner::Organization(organization) => format!("orgs/{}/repos", organization),
+            RepositoryOwner::AuthenticatedUser => "user/repos".to_string(),
+        }
+    }
+
+    fn query(&self) -> &ListRepositoriesQuery {
+        &self.query
+    }
+
+    fn owner(&self) -> Option<&str> {
+        match &self.owner {
+            RepositoryOwner::User(owner) | RepositoryOwner::Organization(owner) => Some(owner),
+            RepositoryOwner::AuthenticatedUser => None,
+        }
+    }
+}
+
+pub type RepositoryPaginator<'a> = Paginator<'a, ListRepositories>;
+
+/// Implement the repository endpoints.
+impl GitHubApi {
+    /// Gets a single repository.
+    pub fn get_repository(&self, owner: &str, repository: &str) -> Response<Repository> {
+        self.get_endpoint(&GetRepository {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+        })
+    }
+
+    /// Creates a paginator over the public repositories of a user.
+    pub fn list_user_repos(
+        &self,
+        username: &str,
+        query: &ListRepositoriesQuery,
+    ) -> RepositoryPaginator<'_> {
+        self.list_repos(RepositoryOwner::User(username.to_string()), query)
+    }
+
+    /// Creates a paginator over the repositories of an organization.
+    pub fn list_org_repos(
+        &self,
+        organization: &str,
+        query: &ListRepositoriesQuery,
+    ) -> RepositoryPaginator<'_> {
+        self.list_repos(
+            RepositoryOwner::Organization(organization.to_string()),
+            query,
+        )
+    }
+
+    /// Creates a paginator over the repositories that the authenticated user can access.
+    pub fn list_authenticated_user_repos(
+        &self,
+        query: &ListRepositoriesQuery,
+    ) -> RepositoryPaginator<'_> {
+        self.list_repos(RepositoryOwner::AuthenticatedUser, query)
+    }
+
+    fn list_repos(
+        &self,
+        owner: RepositoryOwner,
+        query: &ListRepositoriesQuery,
+    ) -> RepositoryPaginator<'_> {
+        self.paginate(ListRepositories {
+            owner,
+            query: query.clone(),
+        })
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Client;
+
+    #[test]
+    fn query_skips_missing_filters() {
+        let query = ListRepositoriesQuery {
+            r#type: Some(RepositoryType::Owner),
+            sort: Some(RepositorySort::FullName),
+            direction: None,
+        };
+
+        let request = Client::new()
+            .get("https://api.github.com/user/repos")
+            .query(&query)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.url().query(), Some("type=owner&sort=full_name"));
+    }
+}
+
+// endregion
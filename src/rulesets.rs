@@ -0,0 +1,380 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::helpers::encode_file_path;
+use crate::impl_repository_scope;
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Where a ruleset lives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RulesetScope {
+    Repository { owner: String, repository: String },
+    Organization(String),
+}
+
+impl RulesetScope {
+    fn path(&self) -> String {
+        match self {
+            RulesetScope::Repository { owner, repository } => {
+                format!("repos/{}/{}/rulesets", owner, repository)
+            }
+            RulesetScope::Organization(organization) => format!("orgs/{}/rulesets", organization),
+        }
+    }
+
+    fn owner(&self) -> Option<&str> {
+        match self {
+            RulesetScope::Repository { owner, .. } => Some(owner),
+            RulesetScope::Organization(organization) => Some(organization),
+        }
+    }
+
+    fn repository(&self) -> Option<&str> {
+        match self {
+            RulesetScope::Repository { repository, .. } => Some(repository),
+            RulesetScope::Organization(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRulesets {
+    pub scope: RulesetScope,
+    pub query: ListRulesetsQuery,
+}
+
+impl Endpoint for ListRulesets {
+    type Response = Vec<Ruleset>;
+    type Query = ListRulesetsQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        self.scope.path()
+    }
+
+    fn query(&self) -> &ListRulesetsQuery {
+        &self.query
+    }
+
+    fn owner(&self) -> Option<&str> {
+        self.scope.owner()
+    }
+
+    fn repository(&self) -> Option<&str> {
+        self.scope.repository()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRuleset {
+    pub scope: RulesetScope,
+    pub id: u64,
+}
+
+impl Endpoint for GetRuleset {
+    type Response = Ruleset;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        format!("{}/{}", self.scope.path(), self.id)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        self.scope.owner()
+    }
+
+    fn repository(&self) -> Option<&str> {
+        self.scope.repository()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateRuleset {
+    pub scope: RulesetScope,
+    pub ruleset: RulesetSpec,
+}
+
+impl Endpoint for CreateRuleset {
+    type Response = Ruleset;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::POST;
+
+    fn path(&self) -> String {
+        self.scope.path()
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.ruleset).ok()
+    }
+
+    fn owner(&self) -> Option<&str> {
+        self.scope.owner()
+    }
+
+    fn repository(&self) -> Option<&str> {
+        self.scope.repository()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRuleset {
+    pub scope: RulesetScope,
+    pub id: u64,
+    pub ruleset: RulesetSpec,
+}
+
+impl Endpoint for UpdateRuleset {
+    type Response = Ruleset;
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::PUT;
+
+    fn path(&self) -> String {
+        format!("{}/{}", self.scope.path(), self.id)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn body(&self) -> Option<Value> {
+        serde_json::to_value(&self.ruleset).ok()
+    }
+
+    fn owner(&self) -> Option<&str> {
+        self.scope.owner()
+    }
+
+    fn repository(&self) -> Option<&str> {
+        self.scope.repository()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteRuleset {
+    pub scope: RulesetScope,
+    pub id: u64,
+}
+
+impl Endpoint for DeleteRuleset {
+    type Response = ();
+    type Query = NoQuery;
+
+    const METHOD: Method = Method::DELETE;
+
+    fn path(&self) -> String {
+        format!("{}/{}", self.scope.path(), self.id)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        self.scope.owner()
+    }
+
+    fn repository(&self) -> Option<&str> {
+        self.scope.repository()
+    }
+}
+
+/// Every rule that applies to a branch, whichever ruleset it comes from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBranchRules {
+    pub owner: String,
+    pub repository: String,
+    pub branch: String,
+}
+
+impl Endpoint for GetBranchRules {
+    type Response = Vec<BranchRule>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!(
+            "repos/{}/{}/rules/branches/{}",
+            self.owner,
+            self.repository,
+            encode_file_path(&self.branch)
+        )
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    impl_repository_scope!();
+}
+
+pub type RulesetPaginator<'a> = Paginator<'a, ListRulesets>;
+pub type BranchRulePaginator<'a> = Paginator<'a, GetBranchRules>;
+
+/// Implement the ruleset endpoints.
+impl GitHubApi {
+    /// Creates a paginator over the rulesets of a repository or an organization.
+    pub fn list_rulesets(
+        &self,
+        scope: &RulesetScope,
+        query: &ListRulesetsQuery,
+    ) -> RulesetPaginator<'_> {
+        self.paginate(ListRulesets {
+            scope: scope.clone(),
+            query: query.clone(),
+        })
+    }
+
+    /// Gets a ruleset, including its conditions and rules.
+    pub fn get_ruleset(&self, scope: &RulesetScope, id: u64) -> Response<Ruleset> {
+        self.get_endpoint(&GetRuleset {
+            scope: scope.clone(),
+            id,
+        })
+    }
+
+    pub fn create_ruleset(&self, scope: &RulesetScope, ruleset: &RulesetSpec) -> Response<Ruleset> {
+        self.get_endpoint(&CreateRuleset {
+            scope: scope.clone(),
+            ruleset: ruleset.clone(),
+        })
+    }
+
+    pub fn update_ruleset(
+        &self,
+        scope: &RulesetScope,
+        id: u64,
+        ruleset: &RulesetSpec,
+    ) -> Response<Ruleset> {
+        self.get_endpoint(&UpdateRuleset {
+            scope: scope.clone(),
+            id,
+            ruleset: ruleset.clone(),
+        })
+    }
+
+    pub fn delete_ruleset(&self, scope: &RulesetScope, id: u64) -> Response<()> {
+        self.get_endpoint(&DeleteRuleset {
+            scope: scope.clone(),
+            id,
+        })
+    }
+
+    /// Creates a paginator over the rules that apply to a branch.
+    /// Only active rulesets count, including the ones of the organization.
+    pub fn get_branch_rules(
+        &self,
+        owner: &str,
+        repository: &str,
+        branch: &str,
+    ) -> BranchRulePaginator<'_> {
+        self.paginate(GetBranchRules {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            branch: branch.to_string(),
+        })
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{parse_json, UncapturedFields};
+
+    #[test]
+    fn rulesets_are_scoped() {
+        let repository = GetRuleset {
+            scope: RulesetScope::Repository {
+                owner: "a".to_string(),
+                repository: "b".to_string(),
+            },
+            id: 42,
+        };
+        let organization = GetRuleset {
+            scope: RulesetScope::Organization("acme".to_string()),
+            id: 42,
+        };
+
+        assert_eq!(repository.path(), "repos/a/b/rulesets/42");
+        assert_eq!(organization.path(), "orgs/acme/rulesets/42");
+        assert_eq!(organization.repository(), None);
+    }
+
+    #[test]
+    fn rulesets_round_trip() {
+        let text = r#"{
+            "id": 42,
+            "name": "main",
+            "target": "branch",
+            "source_type": "Repository",
+            "source": "a/b",
+            "enforcement": "active",
+            "bypass_actors": [{"actor_id": 5, "actor_type": "RepositoryRole", "bypass_mode": "always"}],
+            "conditions": {"ref_name": {"include": ["~DEFAULT_BRANCH"], "exclude": []}},
+            "rules": [
+                {"type": "deletion"},
+                {"type": "pull_request", "parameters": {"required_approving_review_count": 1}}
+            ]
+        }"#;
+
+        let ruleset: Ruleset = parse_json(text).unwrap();
+
+        assert_eq!(
+            ruleset.bypass_actors[0].actor_type,
+            BypassActorType::RepositoryRole
+        );
+        assert_eq!(
+            ruleset.rules[1].parameters.as_ref().unwrap()["required_approving_review_count"],
+            1
+        );
+        assert!(ruleset.uncaptured_fields().is_empty());
+
+        let newer: Vec<BypassActor> = parse_json(
+            r#"[
+                {"actor_id": 1, "actor_type": "EnterpriseOwner", "bypass_mode": "exempt"},
+                {"actor_id": 2, "actor_type": "SomethingNew", "bypass_mode": "sometimes"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(newer[0].actor_type, BypassActorType::EnterpriseOwner);
+        assert_eq!(newer[0].bypass_mode, BypassMode::Exempt);
+        assert_eq!(newer[1].actor_type, BypassActorType::Unknown);
+        assert_eq!(newer[1].bypass_mode, BypassMode::Unknown);
+        assert_eq!(
+            parse_json::<RulesetTarget>(r#""repository""#).unwrap(),
+            RulesetTarget::Repository
+        );
+
+        let spec = RulesetSpec {
+            name: ruleset.name,
+            target: ruleset.target,
+            enforcement: RulesetEnforcement::Evaluate,
+            bypass_actors: ruleset.bypass_actors,
+            conditions: ruleset.conditions,
+            rules: vec![Rule::new("non_fast_forward")],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&spec).unwrap(),
+            r#"{"name":"main","target":"branch","enforcement":"evaluate","bypass_actors":[{"actor_id":5,"actor_type":"RepositoryRole","bypass_mode":"always"}],"conditions":{"ref_name":{"include":["~DEFAULT_BRANCH"],"exclude":[]}},"rules":[{"type":"non_fast_forward"}]}"#
+        );
+    }
+}
+
+// endregion
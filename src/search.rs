@@ -0,0 +1,277 @@
+use crate::endpoint::{Endpoint, PaginationKind, RateLimitBucket};
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchRepositories {
+    pub query: SearchQuery<RepositorySearchSort>,
+}
+
+impl Endpoint for SearchRepositories {
+    type Response = SearchResults<Repository>;
+    type Query = SearchQuery<RepositorySearchSort>;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+    const RATE_LIMIT: RateLimitBucket = RateLimitBucket::Search;
+
+    fn path(&self) -> String {
+        "search/repositories".to_string()
+    }
+
+    fn query(&self) -> &SearchQuery<RepositorySearchSort> {
+        &self.query
+    }
+}
+
+/// Code search needs at least one term besides the qualifiers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchCode {
+    pub query: SearchQuery<CodeSearchSort>,
+}
+
+impl Endpoint for SearchCode {
+    type Response = SearchResults<CodeSearchItem>;
+    type Query = SearchQuery<CodeSearchSort>;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+    const RATE_LIMIT: RateLimitBucket = RateLimitBucket::Search;
+
+    fn path(&self) -> String {
+        "search/code".to_string()
+    }
+
+    fn query(&self) -> &SearchQuery<CodeSearchSort> {
+        &self.query
+    }
+}
+
+/// Searches issues and pull requests. Use `is:issue` or `is:pr` to get only one kind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIssues {
+    pub query: SearchQuery<IssueSearchSort>,
+}
+
+impl Endpoint for SearchIssues {
+    type Response = SearchResults<Issue>;
+    type Query = SearchQuery<IssueSearchSort>;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+    const RATE_LIMIT: RateLimitBucket = RateLimitBucket::Search;
+
+    fn path(&self) -> String {
+        "search/issues".to_string()
+    }
+
+    fn query(&self) -> &SearchQuery<IssueSearchSort> {
+        &self.query
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchUsers {
+    pub query: SearchQuery<UserSearchSort>,
+}
+
+impl Endpoint for SearchUsers {
+    type Response = SearchResults<GenericPerson>;
+    type Query = SearchQuery<UserSearchSort>;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+    const RATE_LIMIT: RateLimitBucket = RateLimitBucket::Search;
+
+    fn path(&self) -> String {
+        "search/users".to_string()
+    }
+
+    fn query(&self) -> &SearchQuery<UserSearchSort> {
+        &self.query
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchCommits {
+    pub query: SearchQuery<CommitSearchSort>,
+}
+
+impl Endpoint for SearchCommits {
+    type Response = SearchResults<Commit>;
+    type Query = SearchQuery<CommitSearchSort>;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+    const RATE_LIMIT: RateLimitBucket = RateLimitBucket::Search;
+
+    fn path(&self) -> String {
+        "search/commits".to_string()
+    }
+
+    fn query(&self) -> &SearchQuery<CommitSearchSort> {
+        &self.query
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchTopics {
+    pub query: TopicSearchQuery,
+}
+
+impl Endpoint for SearchTopics {
+    type Response = SearchResults<Topic>;
+    type Query = TopicSearchQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+    const RATE_LIMIT: RateLimitBucket = RateLimitBucket::Search;
+
+    fn path(&self) -> String {
+        "search/topics".to_string()
+    }
+
+    fn query(&self) -> &TopicSearchQuery {
+        &self.query
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchLabels {
+    pub query: LabelSearchQuery,
+}
+
+impl Endpoint for SearchLabels {
+    type Response = SearchResults<Label>;
+    type Query = LabelSearchQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+    const RATE_LIMIT: RateLimitBucket = RateLimitBucket::Search;
+
+    fn path(&self) -> String {
+        "search/labels".to_string()
+    }
+
+    fn query(&self) -> &LabelSearchQuery {
+        &self.query
+    }
+}
+
+pub type RepositorySearchPaginator<'a> = Paginator<'a, SearchRepositories>;
+pub type CodeSearchPaginator<'a> = Paginator<'a, SearchCode>;
+pub type IssueSearchPaginator<'a> = Paginator<'a, SearchIssues>;
+pub type UserSearchPaginator<'a> = Paginator<'a, SearchUsers>;
+pub type CommitSearchPaginator<'a> = Paginator<'a, SearchCommits>;
+pub type TopicSearchPaginator<'a> = Paginator<'a, SearchTopics>;
+pub type LabelSearchPaginator<'a> = Paginator<'a, SearchLabels>;
+
+/// Implement the search endpoints.
+/// They share a smaller rate limit, so searching waits for it to reset once it runs out.
+impl GitHubApi {
+    pub fn search_repositories(
+        &self,
+        query: &SearchQuery<RepositorySearchSort>,
+    ) -> RepositorySearchPaginator<'_> {
+        self.paginate(SearchRepositories {
+            query: query.clone(),
+        })
+    }
+
+    pub fn search_code(&self, query: &SearchQuery<CodeSearchSort>) -> CodeSearchPaginator<'_> {
+        self.paginate(SearchCode {
+            query: query.clone(),
+        })
+    }
+
+    /// Searches issues and pull requests.
+    pub fn search_issues(&self, query: &SearchQuery<IssueSearchSort>) -> IssueSearchPaginator<'_> {
+        self.paginate(SearchIssues {
+            query: query.clone(),
+        })
+    }
+
+    pub fn search_users(&self, query: &SearchQuery<UserSearchSort>) -> UserSearchPaginator<'_> {
+        self.paginate(SearchUsers {
+            query: query.clone(),
+        })
+    }
+
+    pub fn search_commits(
+        &self,
+        query: &SearchQuery<CommitSearchSort>,
+    ) -> CommitSearchPaginator<'_> {
+        self.paginate(SearchCommits {
+            query: query.clone(),
+        })
+    }
+
+    pub fn search_topics(&self, query: &TopicSearchQuery) -> TopicSearchPaginator<'_> {
+        self.paginate(SearchTopics {
+            query: query.clone(),
+        })
+    }
+
+    /// Searches the labels of the repository with the id in `query`.
+    pub fn search_labels(&self, query: &LabelSearchQuery) -> LabelSearchPaginator<'_> {
+        self.paginate(SearchLabels {
+            query: query.clone(),
+        })
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::UncapturedFields;
+
+    #[test]
+    fn queries_are_quoted_where_needed() {
+        let q = SearchQueryBuilder::new()
+            .term("memory leak")
+            .repo("segfaultsourcery", "githubapi")
+            .is("pr")
+            .label("good first issue")
+            .exclude("label", "wontfix")
+            .qualifier("in", r#"say "hi""#)
+            .created(SearchRange::Between(
+                "2024-01-01".to_string(),
+                "2024-06-30".to_string(),
+            ))
+            .range("comments", SearchRange::Above("10".to_string()))
+            .build();
+
+        assert_eq!(
+            q,
+            r#""memory leak" repo:segfaultsourcery/githubapi is:pr label:"good first issue" -label:wontfix in:"say hi" created:2024-01-01..2024-06-30 comments:>10"#
+        );
+    }
+
+    #[test]
+    fn scores_are_not_uncaptured() {
+        let text = r#"{
+            "total_count": 1,
+            "incomplete_results": false,
+            "items": [
+                {
+                    "name": "rust",
+                    "display_name": "Rust",
+                    "short_description": "A systems language.",
+                    "description": null,
+                    "created_by": null,
+                    "released": "2010",
+                    "created_at": "2016-12-08T00:00:00Z",
+                    "updated_at": "2024-01-01T00:00:00Z",
+                    "featured": true,
+                    "curated": true,
+                    "score": 1.0
+                }
+            ]
+        }"#;
+
+        let results: SearchResults<Topic> = serde_json::from_str(text).unwrap();
+
+        assert_eq!(results.items[0].item.name, "rust");
+        assert_eq!(results.items[0].score, 1.0);
+        assert!(results.uncaptured_fields().is_empty());
+    }
+}
+
+// endregion
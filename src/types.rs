@@ -1,6 +1,8 @@
-use crate::helpers::ToJsonString;
+use crate::helpers::{decode_bytes, decode_content, join_path, ToJsonString, UncapturedFields};
 use reqwest::Error as ReqwestError;
-use serde::{Deserialize, Serialize};
+use reqwest::Url;
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::error::Error as JsonError;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -16,6 +18,32 @@ macro_rules! impl_to_json_string {
     };
 }
 
+#[macro_export]
+macro_rules! impl_uncaptured_fields {
+    (@name $field:ident) => {
+        stringify!($field)
+    };
+    (@name $field:ident $name:literal) => {
+        $name
+    };
+    ($type_name:ty $(, $field:ident $(as $name:literal)?)*) => {
+        impl UncapturedFields for $type_name {
+            fn collect_uncaptured(&self, prefix: &str, paths: &mut Vec<String>) {
+                for key in self.uncaptured.keys() {
+                    paths.push(join_path(prefix, key));
+                }
+
+                $(
+                    self.$field.collect_uncaptured(
+                        &join_path(prefix, impl_uncaptured_fields!(@name $field $($name)?)),
+                        paths,
+                    );
+                )*
+            }
+        }
+    };
+}
+
 // region Envelopes
 
 pub type Response<T> = Result<GitHubApiResult<T>, GitHubApiError>;
@@ -28,6 +56,11 @@ pub struct GitHubApiResult<T> {
     pub owner: Option<String>,
     pub repository: Option<String>,
     pub next_page: Option<u64>,
+    pub next_link: Option<PageLink>,
+    pub last_page: Option<u64>,
+
+    /// The paths of the fields the models don't know about, when the mode is `Warn`.
+    pub schema_drift: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -36,27 +69,149 @@ pub enum GitHubApiError {
     JsonError((JsonError, String)),
     GitHubError((String, String)),
     ReqwestError(ReqwestError),
+    SchemaDrift(Vec<String>),
+    DecodeError(String),
+    InvalidUrl(String),
+    IoError(std::io::Error),
+    UnsafePath(String),
+    InvalidChange(String),
+}
+
+impl GitHubApiError {
+    /// Tells whether GitHub answered with 404 Not Found.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            GitHubApiError::GitHubError((status, _)) => status.starts_with("404"),
+            _ => false,
+        }
+    }
+}
+
+/// The query of endpoints that don't take any parameters besides the page.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NoQuery {}
+
+/// A page that was decoded one item at a time.
+/// Items that could not be decoded end up in `failures` instead of failing the whole page.
+#[derive(Debug)]
+pub struct LenientPage<T> {
+    pub items: Vec<T>,
+    pub failures: Vec<ItemFailure>,
+}
+
+#[derive(Debug)]
+pub struct ItemFailure {
+    /// The position of the item in the page.
+    pub index: usize,
+    pub raw: Value,
+    pub error: JsonError,
 }
 
 // endregion
 
 // region Enums
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OpenClosed {
-    #[serde(rename(deserialize = "open"))]
     Open,
-    #[serde(rename(deserialize = "closed"))]
     Closed,
 }
 
-#[derive(Debug)]
+/// Like `OpenClosed`, but for filters that can also ask for both.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateFilter {
+    Open,
+    Closed,
+    All,
+}
+
+/// What to do when GitHub returns fields that the models don't know about.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaDriftMode {
+    Ignore,
+
+    /// Lists them in `GitHubApiResult.schema_drift`.
+    Warn,
+
+    /// Fails with `GitHubApiError::SchemaDrift`.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RepositoryType {
+    All,
+    Owner,
+    Public,
+    Private,
+    Member,
+    Forks,
+    Sources,
+    Internal,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepositorySort {
+    Created,
+    Updated,
+    Pushed,
+    FullName,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Pagination {
-    First(u64),
-    Previous(u64),
-    Next(u64),
-    Last(u64),
-    Undefined(String, u64),
+    First(PageLink),
+    Previous(PageLink),
+    Next(PageLink),
+    Last(PageLink),
+    Undefined(String, PageLink),
+}
+
+/// Where a paginator continues from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PageCursor {
+    Page(u64),
+
+    /// A link that GitHub handed out, which is followed verbatim.
+    Url(String),
+}
+
+// endregion
+
+// region PageLink
+
+/// A link from the "Link" header.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageLink {
+    pub url: String,
+    pub query: HashMap<String, String>,
+}
+
+impl PageLink {
+    pub fn parse(url: &str) -> Option<Self> {
+        let parsed = Url::parse(url).ok()?;
+        let query = parsed.query_pairs().into_owned().collect();
+
+        Some(Self {
+            url: url.to_string(),
+            query,
+        })
+    }
+
+    /// Gets the page number, if the endpoint uses page numbers rather than cursors.
+    pub fn page(&self) -> Option<u64> {
+        self.query.get("page")?.parse().ok()
+    }
 }
 
 // endregion
@@ -70,8 +225,12 @@ pub struct RateLimitResponse {
     // NOTE: The rate is deprecated by GitHub, and may disappear at any time.
     //       Use `resources.core` instead.
     pub rate: Option<LimitRemainingReset>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(RateLimitResponse);
+impl_uncaptured_fields!(RateLimitResponse, resources, rate);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RateLimitResources {
@@ -79,16 +238,30 @@ pub struct RateLimitResources {
     pub search: LimitRemainingReset,
     pub graphql: LimitRemainingReset,
     pub integration_manifest: LimitRemainingReset,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(RateLimitResources);
+impl_uncaptured_fields!(
+    RateLimitResources,
+    core,
+    search,
+    graphql,
+    integration_manifest
+);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LimitRemainingReset {
     pub limit: u64,
     pub remaining: u64,
     pub reset: u64,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(LimitRemainingReset);
+impl_uncaptured_fields!(LimitRemainingReset);
 
 // endregion
 
@@ -106,6 +279,7 @@ pub struct TagsResponse {
     pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(TagsResponse);
+impl_uncaptured_fields!(TagsResponse, commit);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TagsCommit {
@@ -116,6 +290,7 @@ pub struct TagsCommit {
     pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(TagsCommit);
+impl_uncaptured_fields!(TagsCommit);
 
 // endregion
 
@@ -146,6 +321,7 @@ pub struct ReleasesResponse {
     pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(ReleasesResponse);
+impl_uncaptured_fields!(ReleasesResponse, author, assets);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ReleasesAsset {
@@ -167,6 +343,7 @@ pub struct ReleasesAsset {
     pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(ReleasesAsset);
+impl_uncaptured_fields!(ReleasesAsset, uploader);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GenericPerson {
@@ -193,6 +370,7 @@ pub struct GenericPerson {
     pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(GenericPerson);
+impl_uncaptured_fields!(GenericPerson);
 
 // endregion
 
@@ -219,6 +397,14 @@ pub struct LicenseResponse {
     pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(LicenseResponse);
+impl_uncaptured_fields!(LicenseResponse, links as "_links", license);
+
+impl LicenseResponse {
+    /// Gets the license text, decoded according to `encoding`.
+    pub fn decoded_content(&self) -> Result<String, GitHubApiError> {
+        decode_content(&self.content, &self.encoding)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LicenseLinks {
@@ -231,18 +417,2565 @@ pub struct LicenseLinks {
     pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(LicenseLinks);
+impl_uncaptured_fields!(LicenseLinks);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LicenseLicense {
     pub key: String,
     pub name: String,
     pub spdx_id: String,
-    pub url: String,
+    // NOTE: Licenses that GitHub can't identify are reported as "other", without a url.
+    pub url: Option<String>,
     pub node_id: String,
 
     #[serde(flatten)]
     pub uncaptured: HashMap<String, Value>,
 }
 impl_to_json_string!(LicenseLicense);
+impl_uncaptured_fields!(LicenseLicense);
+
+// endregion
+
+// region License
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct License {
+    pub key: String,
+    pub name: String,
+    pub spdx_id: String,
+    pub url: String,
+    pub node_id: String,
+    pub html_url: String,
+    pub description: String,
+    pub implementation: String,
+    pub permissions: Vec<String>,
+    pub conditions: Vec<String>,
+    pub limitations: Vec<String>,
+    pub body: String,
+    pub featured: bool,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(License);
+impl_uncaptured_fields!(License);
+
+// endregion
+
+// region Repository
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Repository {
+    pub id: u64,
+    pub node_id: String,
+    pub name: String,
+    pub full_name: String,
+    pub owner: GenericPerson,
+    pub private: bool,
+    pub visibility: Option<String>,
+    pub html_url: String,
+    pub description: Option<String>,
+    pub fork: bool,
+    pub url: String,
+    pub archive_url: String,
+    pub assignees_url: String,
+    pub blobs_url: String,
+    pub branches_url: String,
+    pub collaborators_url: String,
+    pub comments_url: String,
+    pub commits_url: String,
+    pub compare_url: String,
+    pub contents_url: String,
+    pub contributors_url: String,
+    pub deployments_url: String,
+    pub downloads_url: String,
+    pub events_url: String,
+    pub forks_url: String,
+    pub git_commits_url: String,
+    pub git_refs_url: String,
+    pub git_tags_url: String,
+    pub git_url: String,
+    pub issue_comment_url: String,
+    pub issue_events_url: String,
+    pub issues_url: String,
+    pub keys_url: String,
+    pub labels_url: String,
+    pub languages_url: String,
+    pub merges_url: String,
+    pub milestones_url: String,
+    pub notifications_url: String,
+    pub pulls_url: String,
+    pub releases_url: String,
+    pub ssh_url: String,
+    pub stargazers_url: String,
+    pub statuses_url: String,
+    pub subscribers_url: String,
+    pub subscription_url: String,
+    pub tags_url: String,
+    pub teams_url: String,
+    pub trees_url: String,
+    pub clone_url: String,
+    pub mirror_url: Option<String>,
+    pub hooks_url: String,
+    pub svn_url: String,
+    pub homepage: Option<String>,
+    pub language: Option<String>,
+    pub forks_count: u64,
+    pub forks: u64,
+    pub stargazers_count: u64,
+    pub watchers_count: u64,
+    pub watchers: u64,
+    pub size: u64,
+    pub default_branch: String,
+    pub open_issues_count: u64,
+    pub open_issues: u64,
+    pub is_template: Option<bool>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    pub has_issues: bool,
+    pub has_projects: bool,
+    pub has_wiki: bool,
+    pub has_pages: bool,
+    pub has_downloads: Option<bool>,
+    pub has_discussions: Option<bool>,
+    pub archived: bool,
+    pub disabled: bool,
+    pub allow_forking: Option<bool>,
+    pub web_commit_signoff_required: Option<bool>,
+    pub pushed_at: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub permissions: Option<RepositoryPermissions>,
+    pub license: Option<LicenseLicense>,
+
+    // NOTE: These are only returned when getting a single repository.
+    pub subscribers_count: Option<u64>,
+    pub network_count: Option<u64>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Repository);
+impl_uncaptured_fields!(Repository, owner, permissions, license);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepositoryPermissions {
+    pub admin: bool,
+    pub maintain: Option<bool>,
+    pub push: bool,
+    pub triage: Option<bool>,
+    pub pull: bool,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(RepositoryPermissions);
+impl_uncaptured_fields!(RepositoryPermissions);
+
+/// Filters for listing repositories. Everything is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListRepositoriesQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<RepositoryType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<RepositorySort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+}
+
+// endregion
+
+// region Issue
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStateReason {
+    Completed,
+    NotPlanned,
+    Reopened,
+    Duplicate,
+
+    /// A reason this crate doesn't know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSort {
+    Created,
+    Updated,
+    Comments,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LockReason {
+    #[serde(rename = "off-topic")]
+    OffTopic,
+    #[serde(rename = "too heated")]
+    TooHeated,
+    #[serde(rename = "resolved")]
+    Resolved,
+    #[serde(rename = "spam")]
+    Spam,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Issue {
+    pub id: u64,
+    pub node_id: String,
+    pub url: String,
+    pub repository_url: String,
+    pub labels_url: String,
+    pub comments_url: String,
+    pub events_url: String,
+    pub html_url: String,
+    pub number: u64,
+    pub state: OpenClosed,
+    pub state_reason: Option<IssueStateReason>,
+    pub title: String,
+    pub body: Option<String>,
+    pub user: GenericPerson,
+    pub labels: Vec<Label>,
+    pub assignee: Option<GenericPerson>,
+    #[serde(default)]
+    pub assignees: Vec<GenericPerson>,
+    pub milestone: Option<Milestone>,
+    pub locked: bool,
+    pub active_lock_reason: Option<LockReason>,
+    pub comments: u64,
+
+    /// Pull requests are issues too. This is only there if the issue is one.
+    pub pull_request: Option<IssuePullRequest>,
+    pub closed_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_by: Option<GenericPerson>,
+    pub author_association: String,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Issue);
+impl_uncaptured_fields!(
+    Issue,
+    user,
+    labels,
+    assignee,
+    assignees,
+    milestone,
+    pull_request,
+    closed_by
+);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssuePullRequest {
+    pub url: String,
+    pub html_url: String,
+    pub diff_url: String,
+    pub patch_url: String,
+    pub merged_at: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(IssuePullRequest);
+impl_uncaptured_fields!(IssuePullRequest);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Label {
+    pub id: u64,
+    pub node_id: String,
+    pub url: String,
+    pub name: String,
+    pub color: String,
+    pub default: bool,
+    pub description: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Label);
+impl_uncaptured_fields!(Label);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Milestone {
+    pub url: String,
+    pub html_url: String,
+    pub labels_url: String,
+    pub id: u64,
+    pub node_id: String,
+    pub number: u64,
+    pub state: OpenClosed,
+    pub title: String,
+    pub description: Option<String>,
+    pub creator: Option<GenericPerson>,
+    pub open_issues: u64,
+    pub closed_issues: u64,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    pub due_on: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Milestone);
+impl_uncaptured_fields!(Milestone, creator);
+
+/// Filters for listing issues. Everything is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListIssuesQuery {
+    /// A milestone number, "*" for any milestone, or "none".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<StateFilter>,
+
+    /// A login, "*" for any assignee, or "none".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mentioned: Option<String>,
+
+    /// Label names, separated by commas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<IssueSort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+
+    /// Only issues updated at or after this time, such as "2020-01-31T00:00:00Z".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NewIssue {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+/// The changes to make to an issue. Fields that are `None` are left alone.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct IssueUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<OpenClosed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_reason: Option<IssueStateReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignees: Option<Vec<String>>,
+
+    /// `Some(None)` removes the milestone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+}
+
+// endregion
+
+// region IssueComment
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentSort {
+    Created,
+    Updated,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub id: u64,
+    pub node_id: String,
+    pub url: String,
+    pub html_url: String,
+    pub issue_url: String,
+    pub body: Option<String>,
+    pub user: GenericPerson,
+    pub created_at: String,
+    pub updated_at: String,
+    pub author_association: String,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(IssueComment);
+impl_uncaptured_fields!(IssueComment, user);
+
+/// Filters for listing issue comments. Everything is optional.
+/// `sort` and `direction` only apply to the comments of a whole repository.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListIssueCommentsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<CommentSort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+
+    /// Only comments updated at or after this time, such as "2020-01-31T00:00:00Z".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+}
+
+// endregion
+
+// region Commit
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Commit {
+    pub url: String,
+    pub sha: String,
+    pub node_id: String,
+    pub html_url: String,
+    pub comments_url: String,
+    pub commit: CommitDetails,
+
+    /// The GitHub accounts, if the emails in `commit` belong to any.
+    pub author: Option<GenericPerson>,
+    pub committer: Option<GenericPerson>,
+    pub parents: Vec<CommitParent>,
+
+    // NOTE: These are only returned when getting a single commit.
+    pub stats: Option<CommitStats>,
+    pub files: Option<Vec<CommitFile>>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Commit);
+impl_uncaptured_fields!(Commit, commit, author, committer, parents, stats, files);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitDetails {
+    pub url: String,
+    pub author: Option<CommitSignature>,
+    pub committer: Option<CommitSignature>,
+    pub message: String,
+    pub tree: TagsCommit,
+    pub comment_count: u64,
+    pub verification: Option<CommitVerification>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CommitDetails);
+impl_uncaptured_fields!(CommitDetails, author, committer, tree, verification);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitSignature {
+    pub name: String,
+    pub email: String,
+
+    /// When creating commits, this is optional, and defaults to now.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CommitSignature);
+impl_uncaptured_fields!(CommitSignature);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitVerification {
+    pub verified: bool,
+    pub reason: String,
+    pub signature: Option<String>,
+    pub payload: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CommitVerification);
+impl_uncaptured_fields!(CommitVerification);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitParent {
+    pub sha: String,
+    pub url: String,
+    pub html_url: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CommitParent);
+impl_uncaptured_fields!(CommitParent);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitStats {
+    pub additions: u64,
+    pub deletions: u64,
+    pub total: u64,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CommitStats);
+impl_uncaptured_fields!(CommitStats);
+
+/// A changed file, in a commit, a comparison or a pull request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitFile {
+    pub sha: Option<String>,
+    pub filename: String,
+
+    /// Such as "added", "removed", "modified" or "renamed".
+    pub status: String,
+    pub additions: u64,
+    pub deletions: u64,
+    pub changes: u64,
+    pub blob_url: Option<String>,
+    pub raw_url: Option<String>,
+    pub contents_url: Option<String>,
+
+    /// This is missing for binary files and very large diffs.
+    pub patch: Option<String>,
+    pub previous_filename: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CommitFile);
+impl_uncaptured_fields!(CommitFile);
+
+/// How the head of a comparison relates to its base.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComparisonStatus {
+    Ahead,
+    Behind,
+    Identical,
+    Diverged,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Comparison {
+    pub url: String,
+    pub html_url: String,
+    pub permalink_url: String,
+    pub diff_url: String,
+    pub patch_url: String,
+    pub base_commit: Commit,
+    pub merge_base_commit: Commit,
+    pub status: ComparisonStatus,
+    pub ahead_by: u64,
+    pub behind_by: u64,
+    pub total_commits: u64,
+    pub commits: Vec<Commit>,
+    pub files: Option<Vec<CommitFile>>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Comparison);
+impl_uncaptured_fields!(Comparison, base_commit, merge_base_commit, commits, files);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusState {
+    Error,
+    Failure,
+    Pending,
+    Success,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitStatus {
+    pub url: String,
+    pub avatar_url: Option<String>,
+    pub id: u64,
+    pub node_id: String,
+    pub state: StatusState,
+    pub description: Option<String>,
+    pub target_url: Option<String>,
+    pub context: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub creator: Option<GenericPerson>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CommitStatus);
+impl_uncaptured_fields!(CommitStatus, creator);
+
+/// The statuses of a commit, with only the latest one of every context.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CombinedStatus {
+    /// "failure" if any status is an error or a failure, "pending" if there are none,
+    /// or if any status is pending, and "success" otherwise.
+    pub state: StatusState,
+    pub statuses: Vec<CommitStatus>,
+    pub sha: String,
+    pub total_count: u64,
+    pub repository: Value,
+    pub commit_url: String,
+    pub url: String,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CombinedStatus);
+impl_uncaptured_fields!(CombinedStatus, statuses);
+
+/// Filters for listing commits. Everything is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListCommitsQuery {
+    /// A SHA or a branch to start listing from. Defaults to the default branch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+
+    /// Only commits that touch this file or directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// A login or an email address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committer: Option<String>,
+
+    /// Such as "2020-01-31T00:00:00Z".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewCommitStatus {
+    pub state: StatusState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Tells statuses apart, such as "ci/build". Defaults to "default".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+// endregion
+
+// region PullRequest
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PullRequestSort {
+    Created,
+    Updated,
+    Popularity,
+    LongRunning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub url: String,
+    pub id: u64,
+    pub node_id: String,
+    pub html_url: String,
+    pub diff_url: String,
+    pub patch_url: String,
+    pub issue_url: String,
+    pub commits_url: String,
+    pub review_comments_url: String,
+    pub review_comment_url: String,
+    pub comments_url: String,
+    pub statuses_url: String,
+    pub number: u64,
+    pub state: OpenClosed,
+    pub locked: bool,
+    pub title: String,
+    pub user: GenericPerson,
+    pub body: Option<String>,
+    pub labels: Vec<Label>,
+    pub milestone: Option<Milestone>,
+    pub active_lock_reason: Option<LockReason>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    pub merged_at: Option<String>,
+    pub merge_commit_sha: Option<String>,
+    pub assignee: Option<GenericPerson>,
+    #[serde(default)]
+    pub assignees: Vec<GenericPerson>,
+    #[serde(default)]
+    pub requested_reviewers: Vec<GenericPerson>,
+    #[serde(default)]
+    pub requested_teams: Vec<Value>,
+    pub head: PullRequestBranch,
+    pub base: PullRequestBranch,
+    #[serde(rename = "_links")]
+    pub links: Value,
+    pub author_association: String,
+    pub auto_merge: Option<Value>,
+    pub draft: Option<bool>,
+
+    // NOTE: These are only returned when getting a single pull request.
+    pub merged: Option<bool>,
+    pub mergeable: Option<bool>,
+    pub rebaseable: Option<bool>,
+    pub mergeable_state: Option<String>,
+    pub merged_by: Option<GenericPerson>,
+    pub comments: Option<u64>,
+    pub review_comments: Option<u64>,
+    pub maintainer_can_modify: Option<bool>,
+    pub commits: Option<u64>,
+    pub additions: Option<u64>,
+    pub deletions: Option<u64>,
+    pub changed_files: Option<u64>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(PullRequest);
+impl_uncaptured_fields!(
+    PullRequest,
+    user,
+    labels,
+    milestone,
+    assignee,
+    assignees,
+    requested_reviewers,
+    head,
+    base,
+    merged_by
+);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PullRequestBranch {
+    pub label: String,
+    pub r#ref: String,
+    pub sha: String,
+    pub user: GenericPerson,
+
+    /// This is `None` if the repository was deleted.
+    pub repo: Option<Repository>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(PullRequestBranch);
+impl_uncaptured_fields!(PullRequestBranch, user, repo);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub sha: String,
+    pub merged: bool,
+    pub message: String,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(MergeResult);
+impl_uncaptured_fields!(MergeResult);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateBranchResult {
+    pub message: String,
+    pub url: String,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(UpdateBranchResult);
+impl_uncaptured_fields!(UpdateBranchResult);
+
+/// Filters for listing pull requests. Everything is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListPullRequestsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<StateFilter>,
+
+    /// Such as "user:branch-name".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<PullRequestSort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NewPullRequest {
+    pub title: String,
+
+    /// The branch with the changes. Use "user:branch-name" for branches in forks.
+    pub head: String,
+
+    /// The branch to merge into.
+    pub base: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintainer_can_modify: Option<bool>,
+}
+
+/// The changes to make to a pull request. Fields that are `None` are left alone.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PullRequestUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<OpenClosed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintainer_can_modify: Option<bool>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PullRequestMerge {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_message: Option<String>,
+
+    /// The merge fails if the head of the pull request isn't this commit anymore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_method: Option<MergeMethod>,
+}
+
+// endregion
+
+// region PullRequestReview
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+}
+
+/// What a review does once it's submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewEvent {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+/// The side of a diff. Deleted lines are on the left, added lines are on the right.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DiffSide {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PullRequestReview {
+    pub id: u64,
+    pub node_id: String,
+    pub user: Option<GenericPerson>,
+    pub body: Option<String>,
+    pub state: ReviewState,
+    pub html_url: String,
+    pub pull_request_url: String,
+    #[serde(rename = "_links")]
+    pub links: Value,
+    pub author_association: String,
+    pub submitted_at: Option<String>,
+    pub commit_id: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(PullRequestReview);
+impl_uncaptured_fields!(PullRequestReview, user);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewComment {
+    pub url: String,
+    pub pull_request_review_id: Option<u64>,
+    pub id: u64,
+    pub node_id: String,
+    pub diff_hunk: String,
+    pub path: String,
+    pub position: Option<u64>,
+    pub original_position: Option<u64>,
+    pub commit_id: String,
+    pub original_commit_id: String,
+    pub in_reply_to_id: Option<u64>,
+    pub user: GenericPerson,
+    pub body: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub html_url: String,
+    pub pull_request_url: String,
+    pub author_association: String,
+    #[serde(rename = "_links")]
+    pub links: Value,
+    pub start_line: Option<u64>,
+    pub original_start_line: Option<u64>,
+    pub start_side: Option<DiffSide>,
+    pub line: Option<u64>,
+    pub original_line: Option<u64>,
+    pub side: Option<DiffSide>,
+    pub subject_type: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ReviewComment);
+impl_uncaptured_fields!(ReviewComment, user);
+
+/// Leave out `event` to create a pending review, and submit it later.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NewReview {
+    /// Defaults to the head of the pull request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<ReviewEvent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<NewReviewComment>,
+}
+
+/// A comment on the lines of a diff.
+/// To comment on several lines, set `start_line` to the first one and `line` to the last one.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NewReviewComment {
+    pub body: String,
+
+    /// Only used when the comment isn't part of a review.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_id: Option<String>,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<DiffSide>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_side: Option<DiffSide>,
+
+    /// Makes this a reply to another review comment. Everything but `body` is then ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_reply_to: Option<u64>,
+}
+
+/// Logins of users, and slugs of teams.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ReviewRequests {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub team_reviewers: Vec<String>,
+}
+
+/// Filters for listing review comments. Everything is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListReviewCommentsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<CommentSort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+
+    /// Only comments updated at or after this time, such as "2020-01-31T00:00:00Z".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+}
+
+// endregion
+
+// region Labels and milestones
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MilestoneSort {
+    DueOn,
+    Completeness,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NewLabel {
+    pub name: String,
+
+    /// A hexadecimal color code, without the leading "#".
+    pub color: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The changes to make to a label. Fields that are `None` are left alone.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LabelUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Filters for listing milestones. Everything is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListMilestonesQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<StateFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<MilestoneSort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NewMilestone {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<OpenClosed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Such as "2020-01-31T00:00:00Z".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_on: Option<String>,
+}
+
+/// The changes to make to a milestone. Fields that are `None` are left alone.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MilestoneUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<OpenClosed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_on: Option<String>,
+}
+
+// endregion
+
+// region Branch
+
+/// A branch, as it's listed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BranchSummary {
+    pub name: String,
+    pub commit: TagsCommit,
+    pub protected: bool,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(BranchSummary);
+impl_uncaptured_fields!(BranchSummary, commit);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Branch {
+    pub name: String,
+    pub commit: Commit,
+    #[serde(rename = "_links")]
+    pub links: Value,
+    pub protected: bool,
+    pub protection: Option<Value>,
+    pub protection_url: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Branch);
+impl_uncaptured_fields!(Branch, commit);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchMerge {
+    pub base: String,
+
+    /// A branch name or a commit SHA.
+    pub head: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BranchProtection {
+    pub url: Option<String>,
+    pub required_status_checks: Option<ProtectionStatusChecks>,
+    pub required_pull_request_reviews: Option<ProtectionReviews>,
+    pub enforce_admins: Option<ProtectionToggle>,
+    pub restrictions: Option<ProtectionRestrictions>,
+    pub required_linear_history: Option<ProtectionToggle>,
+    pub allow_force_pushes: Option<ProtectionToggle>,
+    pub allow_deletions: Option<ProtectionToggle>,
+    pub block_creations: Option<ProtectionToggle>,
+    pub required_conversation_resolution: Option<ProtectionToggle>,
+    pub required_signatures: Option<ProtectionToggle>,
+    pub lock_branch: Option<ProtectionToggle>,
+    pub allow_fork_syncing: Option<ProtectionToggle>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(BranchProtection);
+impl_uncaptured_fields!(
+    BranchProtection,
+    required_status_checks,
+    required_pull_request_reviews,
+    enforce_admins,
+    restrictions,
+    required_linear_history,
+    allow_force_pushes,
+    allow_deletions,
+    block_creations,
+    required_conversation_resolution,
+    required_signatures,
+    lock_branch,
+    allow_fork_syncing
+);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProtectionToggle {
+    pub url: Option<String>,
+    pub enabled: bool,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ProtectionToggle);
+impl_uncaptured_fields!(ProtectionToggle);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProtectionStatusChecks {
+    pub url: Option<String>,
+    pub enforcement_level: Option<String>,
+
+    /// Whether branches must be up to date with the base branch before merging.
+    pub strict: bool,
+    pub contexts: Vec<String>,
+    pub contexts_url: Option<String>,
+    #[serde(default)]
+    pub checks: Vec<StatusCheck>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ProtectionStatusChecks);
+impl_uncaptured_fields!(ProtectionStatusChecks);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusCheck {
+    pub context: String,
+
+    /// Only accepts the status from this app. `None` accepts it from any app.
+    pub app_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProtectionReviews {
+    pub url: Option<String>,
+    pub dismissal_restrictions: Option<Value>,
+    pub bypass_pull_request_allowances: Option<Value>,
+    pub dismiss_stale_reviews: bool,
+    pub require_code_owner_reviews: bool,
+    pub required_approving_review_count: Option<u64>,
+    pub require_last_push_approval: Option<bool>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ProtectionReviews);
+impl_uncaptured_fields!(ProtectionReviews);
+
+/// Who can push to the branch.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProtectionRestrictions {
+    pub url: Option<String>,
+    pub users_url: Option<String>,
+    pub teams_url: Option<String>,
+    pub apps_url: Option<String>,
+    pub users: Vec<GenericPerson>,
+    pub teams: Vec<Value>,
+    #[serde(default)]
+    pub apps: Vec<Value>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ProtectionRestrictions);
+impl_uncaptured_fields!(ProtectionRestrictions, users);
+
+/// Filters for listing branches. Everything is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListBranchesQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+}
+
+/// The whole protection of a branch. Whatever is `None` is turned off.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BranchProtectionUpdate {
+    // NOTE: GitHub requires these four, even when they're null.
+    pub required_status_checks: Option<RequiredStatusChecks>,
+    pub enforce_admins: Option<bool>,
+    pub required_pull_request_reviews: Option<RequiredReviews>,
+    pub restrictions: Option<PushRestrictions>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_linear_history: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_force_pushes: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_deletions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_creations: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_conversation_resolution: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_branch: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_fork_syncing: Option<bool>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RequiredStatusChecks {
+    /// Whether branches must be up to date with the base branch before merging.
+    pub strict: bool,
+
+    /// Always sent, because GitHub needs either this or the deprecated `contexts`.
+    pub checks: Vec<StatusCheck>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RequiredReviews {
+    pub dismiss_stale_reviews: bool,
+    pub require_code_owner_reviews: bool,
+
+    /// Between 0 and 6.
+    pub required_approving_review_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_last_push_approval: Option<bool>,
+}
+
+/// Logins of users, slugs of teams and slugs of apps.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PushRestrictions {
+    pub users: Vec<String>,
+    pub teams: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub apps: Vec<String>,
+}
+
+// endregion
+
+// region Ruleset
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RulesetTarget {
+    Branch,
+    Tag,
+    Push,
+    Repository,
+
+    /// A target this crate doesn't know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RulesetEnforcement {
+    Disabled,
+    Active,
+
+    /// Only reports what would have been blocked. Not available on every plan.
+    Evaluate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BypassActorType {
+    Integration,
+    OrganizationAdmin,
+    RepositoryRole,
+    Team,
+    DeployKey,
+    EnterpriseOwner,
+
+    /// An actor type this crate doesn't know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BypassMode {
+    Always,
+    PullRequest,
+
+    /// Bypasses the rules without them being evaluated at all.
+    Exempt,
+
+    /// A bypass mode this crate doesn't know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Rulesets are listed without their conditions and rules.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Ruleset {
+    pub id: u64,
+    pub name: String,
+    pub target: Option<RulesetTarget>,
+
+    /// "Repository" or "Organization".
+    pub source_type: Option<String>,
+    pub source: String,
+    pub enforcement: RulesetEnforcement,
+    #[serde(default)]
+    pub bypass_actors: Vec<BypassActor>,
+    pub current_user_can_bypass: Option<String>,
+    pub node_id: Option<String>,
+    #[serde(rename = "_links")]
+    pub links: Option<Value>,
+    pub conditions: Option<RulesetConditions>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Ruleset);
+impl_uncaptured_fields!(Ruleset, bypass_actors, conditions, rules);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BypassActor {
+    /// `None` for organization admins.
+    pub actor_id: Option<u64>,
+    pub actor_type: BypassActorType,
+    pub bypass_mode: BypassMode,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(BypassActor);
+impl_uncaptured_fields!(BypassActor);
+
+/// Which refs and repositories a ruleset applies to.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RulesetConditions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ref_name: Option<RefNameCondition>,
+
+    /// Only for organization rulesets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_name: Option<RepositoryNameCondition>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(RulesetConditions);
+impl_uncaptured_fields!(RulesetConditions, ref_name, repository_name);
+
+/// Patterns such as "refs/heads/main", "refs/heads/release/*" or "~DEFAULT_BRANCH".
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RefNameCondition {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(RefNameCondition);
+impl_uncaptured_fields!(RefNameCondition);
+
+/// Patterns such as "api-*", or "~ALL".
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RepositoryNameCondition {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(RepositoryNameCondition);
+impl_uncaptured_fields!(RepositoryNameCondition);
+
+/// A rule, such as "deletion", "non_fast_forward" or "pull_request".
+/// The parameters depend on the type. Use the constructors for the common ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    pub r#type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Value>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Rule);
+impl_uncaptured_fields!(Rule);
+
+impl Rule {
+    /// A rule without parameters, such as "creation", "deletion", "non_fast_forward",
+    /// "required_linear_history" or "required_signatures".
+    pub fn new(r#type: &str) -> Self {
+        Self {
+            r#type: r#type.to_string(),
+            parameters: None,
+            uncaptured: HashMap::new(),
+        }
+    }
+
+    pub fn pull_request(parameters: &PullRequestRuleParameters) -> Self {
+        Self {
+            parameters: serde_json::to_value(parameters).ok(),
+            ..Self::new("pull_request")
+        }
+    }
+
+    pub fn required_status_checks(parameters: &StatusChecksRuleParameters) -> Self {
+        Self {
+            parameters: serde_json::to_value(parameters).ok(),
+            ..Self::new("required_status_checks")
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PullRequestRuleParameters {
+    pub required_approving_review_count: u64,
+    pub dismiss_stale_reviews_on_push: bool,
+    pub require_code_owner_review: bool,
+    pub require_last_push_approval: bool,
+    pub required_review_thread_resolution: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct StatusChecksRuleParameters {
+    pub required_status_checks: Vec<RuleStatusCheck>,
+
+    /// Whether branches must be up to date with the base branch before merging.
+    pub strict_required_status_checks_policy: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RuleStatusCheck {
+    pub context: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integration_id: Option<u64>,
+}
+
+/// A rule that applies to a branch, and the ruleset it comes from.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BranchRule {
+    pub r#type: String,
+    pub parameters: Option<Value>,
+    pub ruleset_source_type: String,
+    pub ruleset_source: String,
+    pub ruleset_id: u64,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(BranchRule);
+impl_uncaptured_fields!(BranchRule);
+
+/// A ruleset to create, or to replace an existing one with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulesetSpec {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<RulesetTarget>,
+    pub enforcement: RulesetEnforcement,
+    pub bypass_actors: Vec<BypassActor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<RulesetConditions>,
+    pub rules: Vec<Rule>,
+}
+
+/// Filters for listing rulesets. Everything is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListRulesetsQuery {
+    /// Also lists the rulesets of the organization that apply to the repository.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub includes_parents: Option<bool>,
+}
+
+// endregion
+
+// region Contents
+
+/// What lives at a path of a repository.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Contents {
+    File(ContentFile),
+    Directory(Vec<ContentEntry>),
+    Symlink(ContentSymlink),
+    Submodule(ContentSubmodule),
+}
+impl_to_json_string!(Contents);
+
+/// Directories are arrays, and everything else is told apart by its "type".
+impl<'de> Deserialize<'de> for Contents {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        let contents = if value.is_array() {
+            serde_json::from_value(value).map(Contents::Directory)
+        } else {
+            match value.get("type").and_then(Value::as_str) {
+                Some("file") => serde_json::from_value(value).map(Contents::File),
+                Some("symlink") => serde_json::from_value(value).map(Contents::Symlink),
+                Some("submodule") => serde_json::from_value(value).map(Contents::Submodule),
+                other => {
+                    return Err(D::Error::custom(format!(
+                        "unknown content type {:?}",
+                        other
+                    )))
+                }
+            }
+        };
+
+        contents.map_err(D::Error::custom)
+    }
+}
+
+impl UncapturedFields for Contents {
+    fn collect_uncaptured(&self, prefix: &str, paths: &mut Vec<String>) {
+        match self {
+            Contents::File(file) => file.collect_uncaptured(prefix, paths),
+            Contents::Directory(entries) => entries.collect_uncaptured(prefix, paths),
+            Contents::Symlink(symlink) => symlink.collect_uncaptured(prefix, paths),
+            Contents::Submodule(submodule) => submodule.collect_uncaptured(prefix, paths),
+        }
+    }
+}
+
+/// Where else an entry of a repository can be found.
+/// Submodules and some directory entries have no Git or HTML url.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentLinks {
+    #[serde(rename = "self")]
+    pub self_link: String,
+    pub git: Option<String>,
+    pub html: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ContentLinks);
+impl_uncaptured_fields!(ContentLinks);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentFile {
+    pub r#type: String,
+
+    /// "base64", or "none" for files over 1 MB, which come without content.
+    pub encoding: String,
+    pub size: u64,
+    pub name: String,
+    pub path: String,
+    pub content: String,
+    pub sha: String,
+    pub url: String,
+    pub git_url: Option<String>,
+    pub html_url: Option<String>,
+    pub download_url: Option<String>,
+    #[serde(rename = "_links")]
+    pub links: ContentLinks,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ContentFile);
+impl_uncaptured_fields!(ContentFile, links as "_links");
+
+impl ContentFile {
+    /// Gets the file as text, decoded according to `encoding`.
+    pub fn decoded_content(&self) -> Result<String, GitHubApiError> {
+        decode_content(&self.content, &self.encoding)
+    }
+}
+
+/// An entry of a directory listing, which comes without content.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentEntry {
+    /// "file", "dir", "symlink" or "submodule".
+    pub r#type: String,
+    pub size: u64,
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub url: String,
+    pub git_url: Option<String>,
+    pub html_url: Option<String>,
+    pub download_url: Option<String>,
+    #[serde(rename = "_links")]
+    pub links: ContentLinks,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ContentEntry);
+impl_uncaptured_fields!(ContentEntry, links as "_links");
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentSymlink {
+    pub r#type: String,
+    pub target: String,
+    pub size: u64,
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub url: String,
+    pub git_url: Option<String>,
+    pub html_url: Option<String>,
+    pub download_url: Option<String>,
+    #[serde(rename = "_links")]
+    pub links: ContentLinks,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ContentSymlink);
+impl_uncaptured_fields!(ContentSymlink, links as "_links");
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentSubmodule {
+    pub r#type: String,
+    pub submodule_git_url: String,
+    pub size: u64,
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub url: String,
+    pub git_url: Option<String>,
+    pub html_url: Option<String>,
+    pub download_url: Option<String>,
+    #[serde(rename = "_links")]
+    pub links: ContentLinks,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(ContentSubmodule);
+impl_uncaptured_fields!(ContentSubmodule, links as "_links");
+
+/// A commit as Git stores it, without GitHub's additions.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitCommit {
+    pub sha: String,
+    pub node_id: String,
+    pub url: String,
+    pub html_url: String,
+    pub author: CommitSignature,
+    pub committer: CommitSignature,
+    pub message: String,
+    pub tree: TagsCommit,
+    pub parents: Vec<CommitParent>,
+    pub verification: Option<CommitVerification>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(GitCommit);
+impl_uncaptured_fields!(GitCommit, author, committer, tree, parents, verification);
+
+/// The file that was written, and the commit that did it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileCommit {
+    /// This is `None` when the file was deleted.
+    pub content: Option<ContentEntry>,
+    pub commit: GitCommit,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(FileCommit);
+impl_uncaptured_fields!(FileCommit, content, commit);
+
+/// Which commit, branch or tag to read from. Defaults to the default branch.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ContentsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+}
+
+/// The new contents of a file.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FileUpdate {
+    /// The commit message.
+    pub message: String,
+
+    /// The whole file. It's base64 encoded when it's sent.
+    pub content: String,
+
+    /// The blob SHA of the file being replaced. Leave it out to create a new file.
+    /// The update fails if the file changed since, so nothing is overwritten by accident.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+
+    /// Defaults to the default branch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    /// Both default to the authenticated user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<CommitSignature>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committer: Option<CommitSignature>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FileDeletion {
+    /// The commit message.
+    pub message: String,
+
+    /// The blob SHA of the file being deleted.
+    pub sha: String,
+
+    /// Defaults to the default branch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<CommitSignature>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committer: Option<CommitSignature>,
+}
+
+// endregion
+
+// region Archive
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    /// A gzipped tar file.
+    Tarball,
+    Zipball,
+}
+
+// endregion
+
+// region Git data
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitObjectType {
+    Commit,
+    Tree,
+    Blob,
+    Tag,
+}
+
+/// The modes Git writes to a tree.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FileMode {
+    #[serde(rename = "100644")]
+    File,
+    #[serde(rename = "100755")]
+    Executable,
+    #[serde(rename = "040000")]
+    Directory,
+    #[serde(rename = "160000")]
+    Submodule,
+    #[serde(rename = "120000")]
+    Symlink,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum BlobEncoding {
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "base64")]
+    Base64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Blob {
+    pub sha: String,
+    pub node_id: String,
+    pub url: String,
+    pub size: Option<u64>,
+    pub content: String,
+    pub encoding: String,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Blob);
+impl_uncaptured_fields!(Blob);
+
+impl Blob {
+    /// Gets the blob as text, decoded according to `encoding`.
+    pub fn decoded_content(&self) -> Result<String, GitHubApiError> {
+        decode_content(&self.content, &self.encoding)
+    }
+
+    /// Gets the blob as bytes, for files that aren't text.
+    pub fn decoded_bytes(&self) -> Result<Vec<u8>, GitHubApiError> {
+        decode_bytes(&self.content, &self.encoding)
+    }
+}
+
+/// What GitHub answers with after storing a blob.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlobReference {
+    pub sha: String,
+    pub url: String,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(BlobReference);
+impl_uncaptured_fields!(BlobReference);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Tree {
+    pub sha: String,
+    pub url: String,
+    pub tree: Vec<TreeEntry>,
+
+    /// Recursive trees are cut off when they have too many entries.
+    pub truncated: bool,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Tree);
+impl_uncaptured_fields!(Tree, tree);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreeEntry {
+    pub path: String,
+
+    /// Kept as text, since old repositories can have modes such as "100664". See `file_mode`.
+    pub mode: String,
+    pub r#type: GitObjectType,
+    pub sha: String,
+
+    /// Only blobs have a size.
+    pub size: Option<u64>,
+
+    /// Submodules don't have a url.
+    pub url: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(TreeEntry);
+impl_uncaptured_fields!(TreeEntry);
+
+impl TreeEntry {
+    /// Gets the mode, unless it is one that Git no longer writes.
+    pub fn file_mode(&self) -> Option<FileMode> {
+        serde_json::from_value(Value::String(self.mode.clone())).ok()
+    }
+}
+
+/// The object a reference or a tag points to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitObject {
+    pub r#type: GitObjectType,
+    pub sha: String,
+    pub url: String,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(GitObject);
+impl_uncaptured_fields!(GitObject);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitRef {
+    /// The full name, such as "refs/heads/main".
+    pub r#ref: String,
+    pub node_id: String,
+    pub url: String,
+    pub object: GitObject,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(GitRef);
+impl_uncaptured_fields!(GitRef, object);
+
+/// An annotated tag object.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitTag {
+    pub node_id: String,
+    pub tag: String,
+    pub sha: String,
+    pub url: String,
+    pub message: String,
+    pub tagger: CommitSignature,
+    pub object: GitObject,
+    pub verification: Option<CommitVerification>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(GitTag);
+impl_uncaptured_fields!(GitTag, tagger, object, verification);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewBlob {
+    pub content: String,
+    pub encoding: BlobEncoding,
+}
+
+impl NewBlob {
+    pub fn text(content: &str) -> Self {
+        Self {
+            content: content.to_string(),
+            encoding: BlobEncoding::Utf8,
+        }
+    }
+
+    pub fn bytes(content: &[u8]) -> Self {
+        Self {
+            content: base64::encode(content),
+            encoding: BlobEncoding::Base64,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TreeQuery {
+    /// Includes the entries of every subtree.
+    /// GitHub takes any value as yes, so leave it `None` otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recursive: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTree {
+    /// The tree to build on. Without one, entries that aren't listed are left out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_tree: Option<String>,
+    pub tree: Vec<NewTreeEntry>,
+}
+
+/// An entry of a new tree. Give it either a `sha` or the `content`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTreeEntry {
+    pub path: String,
+    pub mode: FileMode,
+    pub r#type: GitObjectType,
+
+    /// `Some(None)` removes the entry from the base tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<Option<String>>,
+
+    /// Text content, which GitHub stores as a new blob.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+impl NewTreeEntry {
+    /// An entry pointing to an existing blob.
+    pub fn blob(path: &str, mode: FileMode, sha: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            mode,
+            r#type: GitObjectType::Blob,
+            sha: Some(Some(sha.to_string())),
+            content: None,
+        }
+    }
+
+    /// An entry with text content.
+    pub fn text(path: &str, mode: FileMode, content: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            mode,
+            r#type: GitObjectType::Blob,
+            sha: None,
+            content: Some(content.to_string()),
+        }
+    }
+
+    /// Removes a file from the base tree.
+    pub fn delete(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            mode: FileMode::File,
+            r#type: GitObjectType::Blob,
+            sha: Some(None),
+            content: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewGitCommit {
+    pub message: String,
+
+    /// The SHA of the tree.
+    pub tree: String,
+
+    /// The SHAs of the parents. Leave it empty for a root commit.
+    pub parents: Vec<String>,
+
+    /// Both default to the authenticated user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<CommitSignature>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committer: Option<CommitSignature>,
+
+    /// An ASCII-armored signature, for signed commits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewRef {
+    /// The full name, such as "refs/heads/main".
+    pub r#ref: String,
+    pub sha: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefUpdate {
+    pub sha: String,
+
+    /// Allows updates that aren't fast-forwards.
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTag {
+    /// The name of the tag, such as "v1.0.0".
+    pub tag: String,
+    pub message: String,
+
+    /// The SHA of the tagged object.
+    pub object: String,
+    pub r#type: GitObjectType,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tagger: Option<CommitSignature>,
+}
+
+// endregion
+
+// region Search
+
+/// A page of search results.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchResults<T> {
+    /// How many results there are in total, not just on this page.
+    pub total_count: u64,
+
+    /// GitHub gives up on queries that take too long, and returns what it found so far.
+    pub incomplete_results: bool,
+    pub items: Vec<SearchHit<T>>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+
+/// A search result, along with how well it matched.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchHit<T> {
+    pub score: f64,
+
+    #[serde(flatten)]
+    pub item: T,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeSearchItem {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub url: String,
+    pub git_url: String,
+    pub html_url: String,
+
+    /// A shortened version of the repository.
+    pub repository: Value,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(CodeSearchItem);
+impl_uncaptured_fields!(CodeSearchItem);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Topic {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub short_description: Option<String>,
+    pub description: Option<String>,
+    pub created_by: Option<String>,
+    pub released: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub featured: bool,
+    pub curated: bool,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Topic);
+impl_uncaptured_fields!(Topic);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RepositorySearchSort {
+    Stars,
+    Forks,
+    HelpWantedIssues,
+    Updated,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeSearchSort {
+    Indexed,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSearchSort {
+    Comments,
+    Reactions,
+    Interactions,
+    Created,
+    Updated,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserSearchSort {
+    Followers,
+    Repositories,
+    Joined,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitSearchSort {
+    AuthorDate,
+    CommitterDate,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelSearchSort {
+    Created,
+    Updated,
+}
+
+/// The query of a search. Use `SearchQueryBuilder` to compose `q`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchQuery<S> {
+    pub q: String,
+
+    /// Defaults to the best match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<S>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<Direction>,
+}
+
+impl<S> Default for SearchQuery<S> {
+    fn default() -> Self {
+        Self {
+            q: String::new(),
+            sort: None,
+            order: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TopicSearchQuery {
+    pub q: String,
+}
+
+/// Labels can only be searched within a single repository.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LabelSearchQuery {
+    pub repository_id: u64,
+    pub q: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<LabelSearchSort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<Direction>,
+}
+
+/// A range of dates or numbers, for qualifiers such as `created:` or `stars:`.
+/// Dates are written as "YYYY-MM-DD".
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchRange {
+    Above(String),
+    AtLeast(String),
+    Below(String),
+    AtMost(String),
+
+    /// Both ends are included.
+    Between(String, String),
+}
+
+impl SearchRange {
+    pub fn to_query(&self) -> String {
+        match self {
+            SearchRange::Above(value) => format!(">{}", value),
+            SearchRange::AtLeast(value) => format!(">={}", value),
+            SearchRange::Below(value) => format!("<{}", value),
+            SearchRange::AtMost(value) => format!("<={}", value),
+            SearchRange::Between(from, to) => format!("{}..{}", from, to),
+        }
+    }
+}
+
+/// Composes the `q` of a search out of terms and qualifiers, quoting values where needed.
+///
+/// ```
+/// use githubapi::{SearchQueryBuilder, SearchRange};
+///
+/// let q = SearchQueryBuilder::new()
+///     .term("memory leak")
+///     .repo("segfaultsourcery", "githubapi")
+///     .is("pr")
+///     .label("good first issue")
+///     .created(SearchRange::AtLeast("2024-01-01".to_string()))
+///     .build();
+///
+/// assert_eq!(
+///     q,
+///     r#""memory leak" repo:segfaultsourcery/githubapi is:pr label:"good first issue" created:>=2024-01-01"#
+/// );
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SearchQueryBuilder {
+    parts: Vec<String>,
+}
+
+impl SearchQueryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds words to look for. Several words are searched for as a phrase.
+    pub fn term(mut self, text: &str) -> Self {
+        self.parts.push(quote_search_value(text));
+        self
+    }
+
+    /// Adds any qualifier, such as `language:rust`.
+    pub fn qualifier(mut self, key: &str, value: &str) -> Self {
+        self.parts
+            .push(format!("{}:{}", key, quote_search_value(value)));
+        self
+    }
+
+    /// Leaves out the results that match a qualifier, such as `-label:wontfix`.
+    pub fn exclude(mut self, key: &str, value: &str) -> Self {
+        self.parts
+            .push(format!("-{}:{}", key, quote_search_value(value)));
+        self
+    }
+
+    pub fn range(mut self, key: &str, range: SearchRange) -> Self {
+        self.parts.push(format!("{}:{}", key, range.to_query()));
+        self
+    }
+
+    pub fn repo(self, owner: &str, repository: &str) -> Self {
+        self.qualifier("repo", &format!("{}/{}", owner, repository))
+    }
+
+    pub fn org(self, organization: &str) -> Self {
+        self.qualifier("org", organization)
+    }
+
+    pub fn user(self, user: &str) -> Self {
+        self.qualifier("user", user)
+    }
+
+    /// Such as `is:pr`, `is:open` or `is:merged`.
+    pub fn is(self, value: &str) -> Self {
+        self.qualifier("is", value)
+    }
+
+    pub fn label(self, label: &str) -> Self {
+        self.qualifier("label", label)
+    }
+
+    pub fn language(self, language: &str) -> Self {
+        self.qualifier("language", language)
+    }
+
+    pub fn author(self, author: &str) -> Self {
+        self.qualifier("author", author)
+    }
+
+    pub fn created(self, range: SearchRange) -> Self {
+        self.range("created", range)
+    }
+
+    pub fn updated(self, range: SearchRange) -> Self {
+        self.range("updated", range)
+    }
+
+    pub fn build(&self) -> String {
+        self.parts.join(" ")
+    }
+}
+
+/// Quotes a value if it has anything that would end it early, such as spaces.
+/// Search queries can't escape quotes, so those are left out.
+fn quote_search_value(value: &str) -> String {
+    let value: String = value.chars().filter(|it| *it != '"').collect();
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|it| it.is_whitespace() || it == ':' || it == '(' || it == ')');
+
+    if needs_quotes {
+        format!("\"{}\"", value)
+    } else {
+        value
+    }
+}
+
+// endregion
+
+// region User
+
+/// A user, with the profile that `GenericPerson` leaves out.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct User {
+    pub login: String,
+    pub id: u64,
+    pub node_id: String,
+    pub avatar_url: String,
+    pub gravatar_id: Option<String>,
+    pub url: String,
+    pub html_url: String,
+    pub followers_url: String,
+    pub following_url: String,
+    pub gists_url: String,
+    pub starred_url: String,
+    pub subscriptions_url: String,
+    pub organizations_url: String,
+    pub repos_url: String,
+    pub events_url: String,
+    pub received_events_url: String,
+    pub r#type: String,
+    pub site_admin: bool,
+    pub name: Option<String>,
+    pub company: Option<String>,
+    pub blog: Option<String>,
+    pub location: Option<String>,
+    pub email: Option<String>,
+    pub hireable: Option<bool>,
+    pub bio: Option<String>,
+    pub twitter_username: Option<String>,
+    pub public_repos: u64,
+    pub public_gists: u64,
+    pub followers: u64,
+    pub following: u64,
+    pub created_at: String,
+    pub updated_at: String,
+
+    // NOTE: These are only returned for the authenticated user.
+    pub private_gists: Option<u64>,
+    pub total_private_repos: Option<u64>,
+    pub owned_private_repos: Option<u64>,
+    pub disk_usage: Option<u64>,
+    pub collaborators: Option<u64>,
+    pub two_factor_authentication: Option<bool>,
+    pub plan: Option<Value>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(User);
+impl_uncaptured_fields!(User);
+
+// endregion
+
+// region Organization
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Organization {
+    pub login: String,
+    pub id: u64,
+    pub node_id: String,
+    pub url: String,
+    pub repos_url: String,
+    pub events_url: String,
+    pub hooks_url: String,
+    pub issues_url: String,
+    pub members_url: String,
+    pub public_members_url: String,
+    pub avatar_url: String,
+    pub description: Option<String>,
+    pub name: Option<String>,
+    pub company: Option<String>,
+    pub blog: Option<String>,
+    pub location: Option<String>,
+    pub email: Option<String>,
+    pub twitter_username: Option<String>,
+    pub is_verified: Option<bool>,
+    pub has_organization_projects: bool,
+    pub has_repository_projects: bool,
+    pub public_repos: u64,
+    pub public_gists: u64,
+    pub followers: u64,
+    pub following: u64,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub r#type: String,
+
+    // NOTE: These are only returned to the members of the organization.
+    pub total_private_repos: Option<u64>,
+    pub owned_private_repos: Option<u64>,
+    pub private_gists: Option<u64>,
+    pub disk_usage: Option<u64>,
+    pub collaborators: Option<u64>,
+    pub billing_email: Option<String>,
+    pub default_repository_permission: Option<String>,
+    pub members_can_create_repositories: Option<bool>,
+    pub two_factor_requirement_enabled: Option<bool>,
+    pub plan: Option<Value>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(Organization);
+impl_uncaptured_fields!(Organization);
+
+/// The short form of an organization, as it appears in other responses.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrganizationSummary {
+    pub login: String,
+    pub id: u64,
+    pub node_id: String,
+    pub url: String,
+    pub repos_url: String,
+    pub events_url: String,
+    pub hooks_url: String,
+    pub issues_url: String,
+    pub members_url: String,
+    pub public_members_url: String,
+    pub avatar_url: String,
+    pub description: Option<String>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(OrganizationSummary);
+impl_uncaptured_fields!(OrganizationSummary);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrganizationRole {
+    Admin,
+    Member,
+    BillingManager,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MembershipState {
+    Active,
+
+    /// The user was invited, but hasn't accepted yet.
+    Pending,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrganizationMembership {
+    pub url: String,
+    pub state: MembershipState,
+    pub role: OrganizationRole,
+    pub organization_url: String,
+    pub organization: OrganizationSummary,
+    pub user: Option<GenericPerson>,
+
+    #[serde(flatten)]
+    pub uncaptured: HashMap<String, Value>,
+}
+impl_to_json_string!(OrganizationMembership);
+impl_uncaptured_fields!(OrganizationMembership, organization, user);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberRoleFilter {
+    All,
+    Admin,
+    Member,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum MemberFilter {
+    #[serde(rename = "2fa_disabled")]
+    TwoFactorDisabled,
+    #[serde(rename = "all")]
+    All,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListMembersQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<MemberFilter>,
+
+    /// Defaults to all.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<MemberRoleFilter>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListOutsideCollaboratorsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<MemberFilter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembershipUpdate {
+    pub role: OrganizationRole,
+}
 
 // endregion
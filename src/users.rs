@@ -0,0 +1,138 @@
+use crate::endpoint::{Endpoint, PaginationKind};
+use crate::paginator::Paginator;
+use crate::types::*;
+use crate::GitHubApi;
+use serde::{Deserialize, Serialize};
+
+/// The path of a user, where `None` is the authenticated user.
+fn user_path(user: &Option<String>) -> String {
+    match user {
+        Some(username) => format!("users/{}", username),
+        None => "user".to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetUser {
+    /// `None` gets the authenticated user, along with their private details.
+    pub user: Option<String>,
+}
+
+impl Endpoint for GetUser {
+    type Response = User;
+    type Query = NoQuery;
+
+    fn path(&self) -> String {
+        user_path(&self.user)
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListFollowers {
+    /// `None` lists the followers of the authenticated user.
+    pub user: Option<String>,
+}
+
+impl Endpoint for ListFollowers {
+    type Response = Vec<GenericPerson>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("{}/followers", user_path(&self.user))
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListFollowing {
+    /// `None` lists who the authenticated user follows.
+    pub user: Option<String>,
+}
+
+impl Endpoint for ListFollowing {
+    type Response = Vec<GenericPerson>;
+    type Query = NoQuery;
+
+    const PAGINATION: PaginationKind = PaginationKind::Pages;
+
+    fn path(&self) -> String {
+        format!("{}/following", user_path(&self.user))
+    }
+
+    fn query(&self) -> &NoQuery {
+        &NoQuery {}
+    }
+
+    fn owner(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+}
+
+pub type FollowerPaginator<'a> = Paginator<'a, ListFollowers>;
+pub type FollowingPaginator<'a> = Paginator<'a, ListFollowing>;
+
+/// Implement the user endpoints.
+impl GitHubApi {
+    pub fn get_user(&self, username: &str) -> Response<User> {
+        self.get_endpoint(&GetUser {
+            user: Some(username.to_string()),
+        })
+    }
+
+    /// Gets the user whose credentials are used.
+    pub fn get_authenticated_user(&self) -> Response<User> {
+        self.get_endpoint(&GetUser { user: None })
+    }
+
+    /// Creates a paginator over the followers of a user.
+    pub fn list_followers(&self, username: &str) -> FollowerPaginator<'_> {
+        self.paginate(ListFollowers {
+            user: Some(username.to_string()),
+        })
+    }
+
+    /// Creates a paginator over the users that a user follows.
+    pub fn list_following(&self, username: &str) -> FollowingPaginator<'_> {
+        self.paginate(ListFollowing {
+            user: Some(username.to_string()),
+        })
+    }
+}
+
+// region Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn none_means_the_authenticated_user() {
+        let mine = ListFollowers { user: None };
+        let theirs = ListFollowing {
+            user: Some("octocat".to_string()),
+        };
+
+        assert_eq!(mine.path(), "user/followers");
+        assert_eq!(theirs.path(), "users/octocat/following");
+        assert_eq!(theirs.owner(), Some("octocat"));
+    }
+}
+
+// endregion